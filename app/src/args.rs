@@ -1,18 +1,138 @@
+use std::fmt;
 use std::path::PathBuf;
 
+use simple_graph::DuplicateVertexPolicy;
+
 #[derive(argh::FromArgs)]
-/// Reads a graph from the specified file and applies the search algorithm
-/// from the specified start vertex
+/// Reads a graph from the specified files and runs one of the commands on it
 pub struct Arguments {
-    /// path to file in Trivial Graph Format
+    /// format of the output (text, json or ndjson), text by default
+    #[argh(option, default = "OutputFormat::Text", from_str_fn(parse_output))]
+    pub output: OutputFormat,
+    /// type of the vertex labels (string, int, float or bool), string by default
+    #[argh(option, default = "LabelType::String", from_str_fn(parse_label_type))]
+    pub vertex_type: LabelType,
+    /// type of the edge labels (string, int, float or bool), string by default
+    #[argh(option, default = "LabelType::String", from_str_fn(parse_label_type))]
+    pub edge_type: LabelType,
+    /// format of the input (tgf, gml or pajek), detected by extension or content by default
+    #[argh(option, from_str_fn(parse_format))]
+    pub format: Option<Format>,
+    /// policy for vertices found in several files (error, keep-first or keep-last), error by default
+    #[argh(
+        option,
+        default = "DuplicateVertexPolicy::Error",
+        from_str_fn(parse_conflict)
+    )]
+    pub on_conflict: DuplicateVertexPolicy,
+    #[argh(subcommand)]
+    pub command: Command,
+}
+
+#[derive(argh::FromArgs)]
+#[argh(subcommand)]
+pub enum Command {
+    Traverse(TraverseArguments),
+    Convert(ConvertArguments),
+    Stats(StatsArguments),
+    Path(PathArguments),
+    Validate(ValidateArguments),
+    Diff(DiffArguments),
+    Shell(ShellArguments),
+}
+
+#[derive(argh::FromArgs)]
+/// Applies the search algorithm to the graph from the specified start vertex
+#[argh(subcommand, name = "traverse")]
+pub struct TraverseArguments {
+    /// files with the graph which are merged into one, stdin is read if there are no files or `-` follows `--`
     #[argh(positional)]
-    pub file: PathBuf,
+    pub files: Vec<PathBuf>,
     /// algorithm to process graph (bfs or dfs)
     #[argh(option, from_str_fn(parse_algorithm))]
     pub algorithm: AlgorithmType,
     /// start vertex name in the graph
     #[argh(option)]
     pub start_vertex: String,
+    /// draw the whole graph before the traversal, readable only for small acyclic graphs
+    #[argh(switch)]
+    pub draw: bool,
+    /// draw pictures with ASCII characters instead of Unicode box drawing
+    #[argh(switch)]
+    pub ascii: bool,
+}
+
+#[derive(argh::FromArgs)]
+/// Converts the graph from one format into another
+#[argh(subcommand, name = "convert")]
+pub struct ConvertArguments {
+    /// files with the graph which are merged into one, stdin is read if there are no files or `-` follows `--`
+    #[argh(positional)]
+    pub files: Vec<PathBuf>,
+    /// format of the output (tgf, gml, pajek, dot, json, graphml, gexf, mermaid, plantuml or svg)
+    #[argh(option, from_str_fn(parse_format))]
+    pub to: Format,
+    /// path to the output file, the graph is written to stdout if it's not specified
+    #[argh(option, short = 'o')]
+    pub out: Option<PathBuf>,
+}
+
+#[derive(argh::FromArgs)]
+/// Prints statistics of the graph
+#[argh(subcommand, name = "stats")]
+pub struct StatsArguments {
+    /// files with the graph which are merged into one, stdin is read if there are no files or `-` follows `--`
+    #[argh(positional)]
+    pub files: Vec<PathBuf>,
+}
+
+#[derive(argh::FromArgs)]
+/// Finds the shortest path between two vertices, numeric labels of the edges are used
+/// as their lengths, otherwise every edge has length 1
+#[argh(subcommand, name = "path")]
+pub struct PathArguments {
+    /// files with the graph which are merged into one, stdin is read if there are no files or `-` follows `--`
+    #[argh(positional)]
+    pub files: Vec<PathBuf>,
+    /// name of the first vertex of the path
+    #[argh(option)]
+    pub from: String,
+    /// name of the last vertex of the path
+    #[argh(option)]
+    pub to: String,
+}
+
+#[derive(argh::FromArgs)]
+/// Checks that the file contains a valid graph and reports all problems
+#[argh(subcommand, name = "validate")]
+pub struct ValidateArguments {
+    /// files with the graph which are merged into one, stdin is read if there are no files or `-` follows `--`
+    #[argh(positional)]
+    pub files: Vec<PathBuf>,
+}
+
+#[derive(argh::FromArgs)]
+/// Prints vertices and edges which were added, removed or relabelled in the new graph
+#[argh(subcommand, name = "diff")]
+pub struct DiffArguments {
+    /// file with the old graph, `-` after `--` means stdin
+    #[argh(positional)]
+    pub old: PathBuf,
+    /// file with the new graph, `-` after `--` means stdin
+    #[argh(positional)]
+    pub new: PathBuf,
+    /// path to the patch file, the patch is written to stdout if it's not specified
+    #[argh(option, short = 'o')]
+    pub out: Option<PathBuf>,
+}
+
+#[derive(argh::FromArgs)]
+/// Loads the graph once and runs commands entered interactively, type `help` for the list
+#[argh(subcommand, name = "shell")]
+pub struct ShellArguments {
+    /// files with the graph which are merged into one, stdin can't be used because commands are read from it
+    #[argh(positional)]
+    pub files: Vec<PathBuf>,
 }
 
 #[derive(Debug)]
@@ -28,3 +148,102 @@ fn parse_algorithm(value: &str) -> Result<AlgorithmType, String> {
         _ => Err("unknown algorithm type, only bfs and dfs is available".into()),
     }
 }
+
+/// Format of the data printed to stdout
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum OutputFormat {
+    /// colored text for humans
+    #[default]
+    Text,
+    /// single JSON array with all records
+    Json,
+    /// one JSON record per line
+    Ndjson,
+}
+
+fn parse_output(value: &str) -> Result<OutputFormat, String> {
+    match value {
+        "text" => Ok(OutputFormat::Text),
+        "json" => Ok(OutputFormat::Json),
+        "ndjson" => Ok(OutputFormat::Ndjson),
+        _ => Err("unknown output format, only json, ndjson and text is available".into()),
+    }
+}
+
+/// Type of the labels which is used to instantiate the graph
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LabelType {
+    String,
+    Int,
+    Float,
+    Bool,
+}
+
+fn parse_label_type(value: &str) -> Result<LabelType, String> {
+    match value {
+        "string" => Ok(LabelType::String),
+        "int" => Ok(LabelType::Int),
+        "float" => Ok(LabelType::Float),
+        "bool" => Ok(LabelType::Bool),
+        _ => Err("unknown label type, only string, int, float and bool is available".into()),
+    }
+}
+
+fn parse_conflict(value: &str) -> Result<DuplicateVertexPolicy, String> {
+    match value {
+        "error" => Ok(DuplicateVertexPolicy::Error),
+        "keep-first" => Ok(DuplicateVertexPolicy::KeepFirst),
+        "keep-last" => Ok(DuplicateVertexPolicy::KeepLast),
+        _ => {
+            Err("unknown conflict policy, only error, keep-first and keep-last is available".into())
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    Tgf,
+    Gml,
+    Pajek,
+    Dot,
+    Json,
+    GraphMl,
+    Gexf,
+    Mermaid,
+    PlantUml,
+    Svg,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Format::Tgf => "tgf",
+            Format::Gml => "gml",
+            Format::Pajek => "pajek",
+            Format::Dot => "dot",
+            Format::Json => "json",
+            Format::GraphMl => "graphml",
+            Format::Gexf => "gexf",
+            Format::Mermaid => "mermaid",
+            Format::PlantUml => "plantuml",
+            Format::Svg => "svg",
+        };
+        f.write_str(name)
+    }
+}
+
+fn parse_format(value: &str) -> Result<Format, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "tgf" => Ok(Format::Tgf),
+        "gml" => Ok(Format::Gml),
+        "pajek" | "net" => Ok(Format::Pajek),
+        "dot" | "gv" => Ok(Format::Dot),
+        "json" => Ok(Format::Json),
+        "graphml" => Ok(Format::GraphMl),
+        "gexf" => Ok(Format::Gexf),
+        "mermaid" | "mmd" => Ok(Format::Mermaid),
+        "plantuml" | "puml" => Ok(Format::PlantUml),
+        "svg" => Ok(Format::Svg),
+        _ => Err(format!("unknown format {value:?}")),
+    }
+}
@@ -0,0 +1,428 @@
+use std::fs;
+
+use simple_graph::{
+    BoxDrawing, Charset, DegreeDistribution, Dot, Gexf, Gml, Graph, GraphMl, Json, Mermaid, Pajek,
+    PlantUml, Svg, Traversal, VertexId,
+};
+
+use colored::*;
+
+use crate::*;
+
+/// Diameter takes quadratic time, so it's computed only for graphs up to this size
+const DIAMETER_LIMIT: usize = 1000;
+
+/// Formats adjacent vertices with labels of the edges as `vertex (edge), ...`
+pub fn neighbours_text<V: TypedLabel, E: TypedLabel>(adjacent_vertices: &[(&V, &E)]) -> String {
+    adjacent_vertices
+        .iter()
+        .map(|(vertex, edge)| format!("{vertex} ({edge})"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Converts adjacent vertices and labels of the edges into JSON array
+fn neighbours_json<V: TypedLabel, E: TypedLabel>(adjacent_vertices: &[(&V, &E)]) -> JsonValue {
+    adjacent_vertices
+        .iter()
+        .map(|(vertex, edge)| {
+            JsonValue::object([
+                ("label", vertex.to_string().into()),
+                ("edge", edge.to_string().into()),
+            ])
+        })
+        .collect()
+}
+
+pub fn traverse<V: TypedLabel, E: TypedLabel>(
+    input: &Input,
+    arguments: TraverseArguments,
+    printer: &mut Printer,
+) -> Result<()> {
+    let TraverseArguments {
+        files,
+        algorithm,
+        start_vertex,
+        draw,
+        ascii,
+    } = arguments;
+    let graph = input.read_graph::<V, E>(&files)?;
+
+    let vertex_id = graph.get_vertex_id(&parse_label(&start_vertex)?);
+    let _ = graph.get_vertex(vertex_id)?; //try to find this in graph first
+
+    let charset = match ascii {
+        true => Charset::Ascii,
+        false => Charset::Unicode,
+    };
+
+    match printer.is_text() {
+        true => {
+            if draw {
+                println!("{}", "Vertices of the graph...".to_string().bright_green());
+                print!("{}", BoxDrawing::new(&graph).charset(charset));
+            }
+
+            println!(
+                "{}",
+                format!("Applying \"{algorithm:?}\" algorithm to this graph...").bright_green()
+            );
+        }
+        false => {
+            for (vertex, adjacent_vertices) in graph.vertices()? {
+                printer.record(JsonValue::object([
+                    ("type", "vertex".into()),
+                    ("label", vertex.to_string().into()),
+                    ("neighbours", neighbours_json(&adjacent_vertices)),
+                ]));
+            }
+        }
+    }
+
+    let mut visited = Vec::new();
+    let visitor_fn = |vertex: &V, adjacent_vertices: Vec<(&V, &E)>| {
+        visited.push((
+            vertex.to_string(),
+            neighbours_text(&adjacent_vertices),
+            neighbours_json(&adjacent_vertices),
+        ));
+    };
+
+    let traversal = match algorithm {
+        AlgorithmType::BreadthFirstSearch => {
+            graph.bfs(vertex_id, visitor_fn)?;
+            Traversal::BreadthFirst
+        }
+        AlgorithmType::DepthFirstSearch => {
+            graph.dfs(vertex_id, visitor_fn)?;
+            Traversal::DepthFirst
+        }
+    };
+
+    let name = match algorithm {
+        AlgorithmType::BreadthFirstSearch => "bfs",
+        AlgorithmType::DepthFirstSearch => "dfs",
+    };
+    for (i, (vertex, text, neighbours)) in (1_usize..).zip(visited) {
+        match printer.is_text() {
+            true if text.is_empty() => println!("{i}. {}", vertex.bright_yellow()),
+            true => println!("{i}. {}: {}", vertex.bright_yellow(), text.bright_cyan()),
+            false => printer.record(JsonValue::object([
+                ("type", "visit".into()),
+                ("algorithm", name.into()),
+                ("order", i.into()),
+                ("vertex", vertex.into()),
+                ("neighbours", neighbours),
+            ])),
+        }
+    }
+    if !printer.is_text() {
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        "Spanning tree of the traversal..."
+            .to_string()
+            .bright_green()
+    );
+    let tree = graph.spanning_tree(vertex_id, traversal)?.charset(charset);
+    print!("{}", tree.to_string().bright_cyan());
+
+    Ok(())
+}
+
+pub fn convert<V: TypedLabel, E: TypedLabel>(
+    input: &Input,
+    arguments: ConvertArguments,
+    printer: &mut Printer,
+) -> Result<()> {
+    let ConvertArguments { files, to, out } = arguments;
+    let graph = input.read_graph::<V, E>(&files)?;
+
+    let converted = match to {
+        Format::Tgf => graph.to_string(),
+        Format::Gml => Gml(&graph).to_string(),
+        Format::Pajek => Pajek(&graph).to_string(),
+        Format::Dot => Dot(&graph).to_string(),
+        Format::Json => Json(&graph).to_string(),
+        Format::GraphMl => GraphMl(&graph).to_string(),
+        Format::Gexf => Gexf(&graph).to_string(),
+        Format::Mermaid => Mermaid::new(&graph).to_string(),
+        Format::PlantUml => PlantUml::new(&graph).to_string(),
+        Format::Svg => Svg::new(&graph).to_string(),
+    };
+
+    match out {
+        Some(path) => {
+            fs::write(&path, converted)?;
+            printer.record(JsonValue::object([
+                ("type", "converted".into()),
+                ("format", to.to_string().into()),
+                ("file", path.display().to_string().into()),
+            ]));
+        }
+        None if printer.is_text() => print!("{converted}"),
+        None => printer.record(JsonValue::object([
+            ("type", "converted".into()),
+            ("format", to.to_string().into()),
+            ("content", converted.into()),
+        ])),
+    }
+    Ok(())
+}
+
+pub fn stats<V: TypedLabel, E: TypedLabel>(
+    input: &Input,
+    arguments: StatsArguments,
+    printer: &mut Printer,
+) -> Result<()> {
+    let graph = input.read_graph::<V, E>(&arguments.files)?;
+
+    let out_degrees = graph.out_degree_distribution();
+    let in_degrees = graph.in_degree_distribution();
+    let weak = graph.weakly_connected_components().len();
+    let strong = graph.strongly_connected_components().len();
+    let labels = |ids: Vec<VertexId>| {
+        ids.into_iter()
+            .map(|vertex_id| graph.get_vertex(vertex_id).map(ToString::to_string))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let self_loops = labels(graph.self_loops())?;
+    let isolated = labels(graph.isolated_vertices())?;
+    let is_dag = graph.is_dag();
+    let diameter = match graph.vertices_count() <= DIAMETER_LIMIT {
+        true => Some(graph.diameter()),
+        false => None,
+    };
+
+    if !printer.is_text() {
+        let distribution = |degrees: &DegreeDistribution| {
+            JsonValue::object([
+                ("min", degrees.min.into()),
+                ("max", degrees.max.into()),
+                ("mean", degrees.mean.into()),
+                ("histogram", degrees.histogram.iter().copied().collect()),
+            ])
+        };
+        printer.record(JsonValue::object([
+            ("type", "stats".into()),
+            ("vertices", graph.vertices_count().into()),
+            ("edges", graph.edges_count().into()),
+            ("density", graph.density().into()),
+            ("out_degree", distribution(&out_degrees)),
+            ("in_degree", distribution(&in_degrees)),
+            ("weakly_connected_components", weak.into()),
+            ("strongly_connected_components", strong.into()),
+            ("self_loops", self_loops.into_iter().collect()),
+            ("isolated_vertices", isolated.into_iter().collect()),
+            ("is_dag", is_dag.into()),
+            ("diameter", diameter.into()),
+        ]));
+        return Ok(());
+    }
+
+    let distribution = |degrees: &DegreeDistribution| {
+        format!(
+            "min {}, max {}, mean {:.2}, histogram {:?}",
+            degrees.min, degrees.max, degrees.mean, degrees.histogram
+        )
+    };
+    let list = |labels: &[String]| match labels.is_empty() {
+        true => "0".to_string(),
+        false => format!("{} ({})", labels.len(), labels.join(", ")),
+    };
+    println!("{} {}", "vertices:".bright_green(), graph.vertices_count());
+    println!("{} {}", "edges:".bright_green(), graph.edges_count());
+    println!("{} {:.2}", "density:".bright_green(), graph.density());
+    println!(
+        "{} {}",
+        "out-degree:".bright_green(),
+        distribution(&out_degrees)
+    );
+    println!(
+        "{} {}",
+        "in-degree:".bright_green(),
+        distribution(&in_degrees)
+    );
+    println!("{} {weak}", "weakly connected components:".bright_green());
+    println!(
+        "{} {strong}",
+        "strongly connected components:".bright_green()
+    );
+    println!("{} {}", "self-loops:".bright_green(), list(&self_loops));
+    println!(
+        "{} {}",
+        "isolated vertices:".bright_green(),
+        list(&isolated)
+    );
+    println!(
+        "{} {}",
+        "DAG:".bright_green(),
+        if is_dag { "yes" } else { "no" }
+    );
+    match diameter {
+        Some(diameter) => println!("{} {diameter}", "diameter:".bright_green()),
+        None => println!(
+            "{} skipped, the graph has more than {DIAMETER_LIMIT} vertices",
+            "diameter:".bright_green()
+        ),
+    }
+    Ok(())
+}
+
+pub fn path<V: TypedLabel, E: TypedLabel>(
+    input: &Input,
+    arguments: PathArguments,
+    printer: &mut Printer,
+) -> Result<()> {
+    let PathArguments { files, from, to } = arguments;
+    let graph = input.read_graph::<V, E>(&files)?;
+    let (distance, path, weighted) = find_path(&graph, &from, &to)?;
+
+    if !printer.is_text() {
+        printer.record(JsonValue::object([
+            ("type", "path".into()),
+            ("vertices", path.iter().map(|v| v.to_string()).collect()),
+            ("distance", distance.into()),
+            ("weighted", weighted.into()),
+        ]));
+        return Ok(());
+    }
+
+    print_path(distance, &path, weighted);
+    Ok(())
+}
+
+/// Finds the shortest path between vertices with the specified labels and returns its length,
+/// vertices and `true` if numeric labels of the edges were used as their lengths
+pub fn find_path<'a, V: TypedLabel, E: TypedLabel>(
+    graph: &'a Graph<V, E>,
+    from: &str,
+    to: &str,
+) -> Result<(f64, Vec<&'a V>, bool)> {
+    let from_id = graph.get_vertex_id(&parse_label(from)?);
+    let to_id = graph.get_vertex_id(&parse_label(to)?);
+
+    // numeric labels are lengths of the edges, otherwise the path with the fewest edges is found
+    let weighted = graph
+        .edges()?
+        .iter()
+        .all(|(_, edge)| edge.to_string().parse::<f64>().is_ok_and(|w| w >= 0.0));
+    let length = |edge: &E| match weighted {
+        true => edge.to_string().parse::<f64>().unwrap_or(0.0),
+        false => 1.0,
+    };
+
+    let (distance, path) = graph
+        .shortest_path(from_id, to_id, length)?
+        .ok_or_else(|| MyError::NoPath(from.into(), to.into()))?;
+    let path = path
+        .into_iter()
+        .map(|vertex_id| graph.get_vertex(vertex_id))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((distance, path, weighted))
+}
+
+/// Prints the path found by [`find_path`] in text mode
+pub fn print_path<V: TypedLabel>(distance: f64, path: &[&V], weighted: bool) {
+    let path = path
+        .iter()
+        .map(|v| v.to_string().bright_yellow().to_string())
+        .collect::<Vec<_>>();
+    println!("{}", path.join(" -> "));
+    match weighted {
+        true => println!("{} {distance}", "distance:".bright_green()),
+        false => println!("{} {distance}", "edges:".bright_green()),
+    }
+}
+
+pub fn validate<V: TypedLabel, E: TypedLabel>(
+    input: &Input,
+    arguments: ValidateArguments,
+    printer: &mut Printer,
+) -> Result<()> {
+    let graph = input.read_graph::<V, E>(&arguments.files)?;
+    if !printer.is_text() {
+        printer.record(JsonValue::object([
+            ("type", "valid".into()),
+            ("vertices", graph.vertices_count().into()),
+            ("edges", graph.edges_count().into()),
+        ]));
+        return Ok(());
+    }
+    println!(
+        "{} {} vertices, {} edges",
+        "valid:".bright_green(),
+        graph.vertices_count(),
+        graph.edges_count()
+    );
+    Ok(())
+}
+
+pub fn diff<V: TypedLabel, E: TypedLabel>(
+    input: &Input,
+    arguments: DiffArguments,
+    printer: &mut Printer,
+) -> Result<()> {
+    let DiffArguments { old, new, out } = arguments;
+    let old = input.read_graph::<V, E>(&[old])?;
+    let new = input.read_graph::<V, E>(&[new])?;
+    let diff = old.diff(&new);
+
+    if let Some(path) = out {
+        fs::write(&path, diff.to_string())?;
+        printer.record(JsonValue::object([
+            ("type", "diff".into()),
+            ("file", path.display().to_string().into()),
+        ]));
+        return Ok(());
+    }
+
+    if !printer.is_text() {
+        let vertices = |vertices: &[V]| vertices.iter().map(ToString::to_string).collect();
+        let edges = |edges: &[([V; 2], E)]| {
+            edges
+                .iter()
+                .map(|([from, to], edge)| {
+                    JsonValue::object([
+                        ("from", from.to_string().into()),
+                        ("to", to.to_string().into()),
+                        ("label", edge.to_string().into()),
+                    ])
+                })
+                .collect()
+        };
+        let relabelled = diff
+            .relabelled_edges
+            .iter()
+            .map(|([from, to], old, new)| {
+                JsonValue::object([
+                    ("from", from.to_string().into()),
+                    ("to", to.to_string().into()),
+                    ("old", old.to_string().into()),
+                    ("new", new.to_string().into()),
+                ])
+            })
+            .collect();
+        printer.record(JsonValue::object([
+            ("type", "diff".into()),
+            ("added_vertices", vertices(&diff.added_vertices)),
+            ("removed_vertices", vertices(&diff.removed_vertices)),
+            ("added_edges", edges(&diff.added_edges)),
+            ("removed_edges", edges(&diff.removed_edges)),
+            ("relabelled_edges", relabelled),
+        ]));
+        return Ok(());
+    }
+
+    for line in diff.to_string().lines() {
+        match line.chars().next() {
+            Some('+') => println!("{}", line.bright_green()),
+            Some('-') => println!("{}", line.bright_red()),
+            Some('~') => println!("{}", line.bright_yellow()),
+            _ => println!("{line}"),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,349 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+/// Raw mode of the terminal is switched by `stty`, so line editing is available only on unix
+#[cfg(unix)]
+mod raw {
+    use std::io::{self, Read};
+    use std::process::{Command, Stdio};
+
+    /// Runs `stty` on the terminal connected to stdin and returns its output
+    fn stty(args: &[&str]) -> Option<String> {
+        let output = Command::new("stty")
+            .args(args)
+            .stdin(Stdio::inherit())
+            .output()
+            .ok()?;
+        match output.status.success() {
+            true => Some(String::from_utf8_lossy(&output.stdout).trim().into()),
+            false => None,
+        }
+    }
+
+    /// Disables line buffering and echo of the terminal, previous settings are restored on drop
+    pub struct RawMode(String);
+
+    impl RawMode {
+        pub fn enable() -> Option<Self> {
+            let saved = stty(&["-g"])?;
+            stty(&["-icanon", "-echo", "-isig", "min", "1", "time", "0"])?;
+            Some(RawMode(saved))
+        }
+    }
+
+    impl Drop for RawMode {
+        fn drop(&mut self) {
+            stty(&[&self.0]);
+        }
+    }
+
+    pub enum Key {
+        Char(char),
+        Enter,
+        Tab,
+        Backspace,
+        Left,
+        Right,
+        Up,
+        Down,
+        Home,
+        End,
+        /// Ctrl-C
+        Interrupt,
+        /// Ctrl-D
+        Eof,
+        Unknown,
+    }
+
+    fn read_byte(input: &mut impl Read) -> io::Result<Option<u8>> {
+        let mut byte = [0];
+        match input.read(&mut byte)? {
+            0 => Ok(None),
+            _ => Ok(Some(byte[0])),
+        }
+    }
+
+    pub fn read_key(input: &mut impl Read) -> io::Result<Key> {
+        let Some(byte) = read_byte(input)? else {
+            return Ok(Key::Eof);
+        };
+        let key = match byte {
+            b'\r' | b'\n' => Key::Enter,
+            b'\t' => Key::Tab,
+            0x7f | 0x08 => Key::Backspace,
+            0x01 => Key::Home,
+            0x03 => Key::Interrupt,
+            0x04 => Key::Eof,
+            0x05 => Key::End,
+            0x1b => match (read_byte(input)?, read_byte(input)?) {
+                (Some(b'[' | b'O'), Some(b'A')) => Key::Up,
+                (Some(b'[' | b'O'), Some(b'B')) => Key::Down,
+                (Some(b'[' | b'O'), Some(b'C')) => Key::Right,
+                (Some(b'[' | b'O'), Some(b'D')) => Key::Left,
+                (Some(b'[' | b'O'), Some(b'H')) => Key::Home,
+                (Some(b'[' | b'O'), Some(b'F')) => Key::End,
+                (Some(b'['), Some(b'0'..=b'9')) => {
+                    // skip the rest of sequences like `ESC [ 3 ~`
+                    while !matches!(read_byte(input)?, Some(b'~') | None) {}
+                    Key::Unknown
+                }
+                _ => Key::Unknown,
+            },
+            byte if byte < 0x20 => Key::Unknown,
+            byte => {
+                // leading byte of UTF-8 sequence tells how many bytes follow
+                let mut bytes = vec![byte];
+                for _ in 1..byte.leading_ones().max(1) {
+                    bytes.extend(read_byte(input)?);
+                }
+                match String::from_utf8(bytes).ok().and_then(|s| s.chars().next()) {
+                    Some(c) => Key::Char(c),
+                    None => Key::Unknown,
+                }
+            }
+        };
+        Ok(key)
+    }
+}
+
+/// Returns the longest common prefix of all candidates
+#[cfg(unix)]
+fn common_prefix(candidates: &[String]) -> String {
+    let mut prefix = candidates.first().cloned().unwrap_or_default();
+    for candidate in candidates {
+        while !candidate.starts_with(&prefix) {
+            prefix.pop();
+        }
+    }
+    prefix
+}
+
+/// Quotes the word if it can't be used as a single argument of the command
+pub fn quote(word: &str) -> String {
+    match word.is_empty() || word.contains(char::is_whitespace) || word.contains('"') {
+        true => format!("\"{}\"", word.replace('\\', "\\\\").replace('"', "\\\"")),
+        false => word.into(),
+    }
+}
+
+/// Splits the line into words, double quotes group words with spaces and `\` escapes the next character
+///
+/// Unterminated quote is closed at the end of the line, so it can be used for completion
+pub fn split_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = None::<String>;
+    let mut quoted = false;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => word.get_or_insert_default().extend(chars.next()),
+            '"' => {
+                word.get_or_insert_default();
+                quoted = !quoted;
+            }
+            c if c.is_whitespace() && !quoted => words.extend(word.take()),
+            c => word.get_or_insert_default().push(c),
+        }
+    }
+    words.extend(word);
+    words
+}
+
+/// Line editor with history and completion which works in the unix terminal,
+/// otherwise lines are read from stdin as is
+pub struct Editor {
+    history: Vec<String>,
+    interactive: bool,
+}
+
+impl Editor {
+    pub fn new() -> Self {
+        Self {
+            history: Vec::new(),
+            interactive: io::stdin().is_terminal() && io::stdout().is_terminal(),
+        }
+    }
+
+    /// Entered lines, the oldest first
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Reads the next line, returns `None` at the end of input
+    ///
+    /// `complete` gets index of the word under cursor and its beginning,
+    /// and returns all words which can be there
+    pub fn read_line<F>(&mut self, prompt: &str, complete: F) -> io::Result<Option<String>>
+    where
+        F: Fn(usize, &str) -> Vec<String>,
+    {
+        let line = match self.interactive {
+            true => self.read_terminal_line(prompt, complete)?,
+            false => read_plain_line()?,
+        };
+
+        if let Some(line) = &line
+            && !line.trim().is_empty()
+            && self.history.last() != Some(line)
+        {
+            self.history.push(line.clone());
+        }
+        Ok(line)
+    }
+
+    /// Edits the line in raw mode, falls back to the plain line if `stty` fails
+    #[cfg(unix)]
+    fn read_terminal_line<F>(&self, prompt: &str, complete: F) -> io::Result<Option<String>>
+    where
+        F: Fn(usize, &str) -> Vec<String>,
+    {
+        match raw::RawMode::enable() {
+            Some(_raw_mode) => self.edit_line(prompt, complete),
+            None => read_prompted_line(prompt),
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn read_terminal_line<F>(&self, prompt: &str, _complete: F) -> io::Result<Option<String>>
+    where
+        F: Fn(usize, &str) -> Vec<String>,
+    {
+        read_prompted_line(prompt)
+    }
+
+    #[cfg(unix)]
+    fn edit_line<F>(&self, prompt: &str, complete: F) -> io::Result<Option<String>>
+    where
+        F: Fn(usize, &str) -> Vec<String>,
+    {
+        let mut stdin = io::stdin().lock();
+        let mut stdout = io::stdout().lock();
+
+        let mut line = Vec::<char>::new();
+        let mut cursor = 0;
+        let mut position = self.history.len();
+
+        loop {
+            let text = line.iter().collect::<String>();
+            write!(stdout, "\r\x1b[K{prompt}{text}")?;
+            if cursor < line.len() {
+                write!(stdout, "\x1b[{}D", line.len() - cursor)?;
+            }
+            stdout.flush()?;
+
+            match raw::read_key(&mut stdin)? {
+                raw::Key::Char(c) => {
+                    line.insert(cursor, c);
+                    cursor += 1;
+                }
+                raw::Key::Enter => {
+                    writeln!(stdout)?;
+                    return Ok(Some(text));
+                }
+                raw::Key::Eof if line.is_empty() => {
+                    writeln!(stdout)?;
+                    return Ok(None);
+                }
+                raw::Key::Interrupt => {
+                    writeln!(stdout, "^C")?;
+                    line.clear();
+                    cursor = 0;
+                    position = self.history.len();
+                }
+                raw::Key::Backspace if cursor > 0 => {
+                    cursor -= 1;
+                    line.remove(cursor);
+                }
+                raw::Key::Left if cursor > 0 => cursor -= 1,
+                raw::Key::Right if cursor < line.len() => cursor += 1,
+                raw::Key::Home => cursor = 0,
+                raw::Key::End => cursor = line.len(),
+                raw::Key::Up if position > 0 => {
+                    position -= 1;
+                    line = self.history[position].chars().collect();
+                    cursor = line.len();
+                }
+                raw::Key::Down if position < self.history.len() => {
+                    position += 1;
+                    line = match self.history.get(position) {
+                        Some(entry) => entry.chars().collect(),
+                        None => Vec::new(),
+                    };
+                    cursor = line.len();
+                }
+                raw::Key::Tab => {
+                    let start = word_start(&line[..cursor]);
+                    let index = split_words(&line[..start].iter().collect::<String>()).len();
+                    let prefix = split_words(&line[start..cursor].iter().collect::<String>())
+                        .pop()
+                        .unwrap_or_default();
+                    let mut candidates = complete(index, &prefix)
+                        .into_iter()
+                        .filter(|candidate| candidate.starts_with(&prefix))
+                        .collect::<Vec<_>>();
+                    candidates.sort();
+                    candidates.dedup();
+
+                    let completion = match candidates.len() {
+                        0 => continue,
+                        1 => format!("{} ", quote(&candidates[0])),
+                        _ => {
+                            let common = common_prefix(&candidates);
+                            if common.len() == prefix.len() {
+                                writeln!(stdout, "\r\n{}", candidates.join("  "))?;
+                                continue;
+                            }
+                            let quoted = quote(&common);
+                            // keep the quote open, the word is not finished yet
+                            quoted.strip_suffix('"').unwrap_or(&quoted).into()
+                        }
+                    };
+
+                    // replace the word under cursor with its completion
+                    line.splice(start..cursor, completion.chars());
+                    cursor = start + completion.chars().count();
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Default for Editor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Shows the prompt and reads line without editing
+fn read_prompted_line(prompt: &str) -> io::Result<Option<String>> {
+    print!("{prompt}");
+    io::stdout().flush()?;
+    read_plain_line()
+}
+
+/// Reads line without editing, it's used when stdin is not a terminal
+fn read_plain_line() -> io::Result<Option<String>> {
+    let mut line = String::new();
+    match io::stdin().lock().read_line(&mut line)? {
+        0 => Ok(None),
+        _ => Ok(Some(line.trim_end_matches(['\r', '\n']).into())),
+    }
+}
+
+/// Returns index of the first character of the last word
+#[cfg(unix)]
+fn word_start(line: &[char]) -> usize {
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (i, &c) in line.iter().enumerate() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => start = i + 1,
+            _ => {}
+        }
+    }
+    start
+}
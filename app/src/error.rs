@@ -1,5 +1,9 @@
 use std::io;
 
+use simple_graph::TgfDiagnostic;
+
+use crate::{Format, JsonValue};
+
 pub type Result<T, E = MyError> = std::result::Result<T, E>;
 
 #[derive(thiserror::Error, Debug)]
@@ -8,6 +12,80 @@ pub enum MyError {
     IO(#[from] io::Error),
     #[error("graph library error: {0}")]
     GraphOperation(#[from] simple_graph::GraphOperationError),
+    #[error("history error: {0}")]
+    Transaction(#[from] simple_graph::TransactionError),
     #[error("graph parse error: {0}")]
     GraphParse(#[from] simple_graph::ParseGraphError),
+    #[error("graph parse error: found {} problem(s) in the input file", .1.len())]
+    GraphDiagnostics(String, Vec<TgfDiagnostic>),
+    #[error("{0} contains vertices which are already defined in previous files, see --on-conflict")]
+    MergeConflict(String),
+    #[error(
+        "label at line {1} of {0} can't be parsed, vertex labels must be {2} and edge labels must be {3}, see --vertex-type and --edge-type"
+    )]
+    ParseLabel(String, usize, &'static str, &'static str),
+    #[error("{0:?} is not a valid {1} label")]
+    InvalidLabel(String, &'static str),
+    #[error("graphs can't be read from {0} format")]
+    UnreadableFormat(Format),
+    #[error("there is no path from {0:?} to {1:?}")]
+    NoPath(String, String),
+    #[error("{0}")]
+    Shell(String),
+}
+
+impl MyError {
+    /// Short name of the error kind used in machine-readable output
+    fn kind(&self) -> &'static str {
+        match self {
+            MyError::IO(_) => "io",
+            MyError::GraphOperation(_) | MyError::Transaction(_) => "graph",
+            MyError::GraphParse(_) | MyError::GraphDiagnostics(..) | MyError::ParseLabel(..) => {
+                "parse"
+            }
+            MyError::InvalidLabel(..) => "label",
+            MyError::MergeConflict(_) => "conflict",
+            MyError::UnreadableFormat(_) => "format",
+            MyError::NoPath(..) => "no_path",
+            MyError::Shell(_) => "shell",
+        }
+    }
+
+    /// Converts error into JSON records, every problem of the input file becomes separate record
+    pub fn to_json(&self) -> Vec<JsonValue> {
+        match self {
+            MyError::GraphDiagnostics(file, diagnostics) => diagnostics
+                .iter()
+                .map(|diagnostic| {
+                    JsonValue::object([
+                        ("type", "error".into()),
+                        ("kind", self.kind().into()),
+                        ("message", diagnostic.error.to_string().into()),
+                        ("file", file.as_str().into()),
+                        ("line", diagnostic.line.into()),
+                        ("column", diagnostic.column.into()),
+                        ("hint", diagnostic.hint.into()),
+                    ])
+                })
+                .collect(),
+            MyError::ParseLabel(file, line, ..) => vec![JsonValue::object([
+                ("type", "error".into()),
+                ("kind", self.kind().into()),
+                ("message", self.to_string().into()),
+                ("file", file.as_str().into()),
+                ("line", (*line).into()),
+            ])],
+            MyError::GraphParse(error) => vec![JsonValue::object([
+                ("type", "error".into()),
+                ("kind", self.kind().into()),
+                ("message", error.to_string().into()),
+                ("line", error.line().into()),
+            ])],
+            _ => vec![JsonValue::object([
+                ("type", "error".into()),
+                ("kind", self.kind().into()),
+                ("message", self.to_string().into()),
+            ])],
+        }
+    }
 }
@@ -0,0 +1,153 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use simple_graph::{DuplicateVertexPolicy, Graph, ParseGraphError, TgfReader};
+
+use crate::*;
+
+/// Name of the standard input in diagnostics and error messages
+const STDIN: &str = "<stdin>";
+
+/// Source of the graph, `-` means standard input
+enum Source<'a> {
+    Stdin,
+    File(&'a Path),
+}
+
+impl<'a> Source<'a> {
+    fn new(path: &'a Path) -> Self {
+        match path.as_os_str() == "-" {
+            true => Source::Stdin,
+            false => Source::File(path),
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Source::Stdin => STDIN.into(),
+            Source::File(path) => path.display().to_string(),
+        }
+    }
+
+    fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        Ok(match self {
+            Source::Stdin => Box::new(io::stdin().lock()),
+            Source::File(path) => Box::new(BufReader::new(File::open(path)?)),
+        })
+    }
+}
+
+/// Detects format of the graph by extension of the file
+fn detect_by_extension(path: &Path) -> Option<Format> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "tgf" => Some(Format::Tgf),
+        "gml" => Some(Format::Gml),
+        "net" | "paj" | "pajek" => Some(Format::Pajek),
+        _ => None,
+    }
+}
+
+/// Detects format of the graph by the beginning of its content, TGF is used if nothing else matches
+fn detect_by_content(content: &str) -> Format {
+    let mut lines = content.lines().map(str::trim);
+    let first = lines
+        .clone()
+        .find(|line| !line.is_empty() && !line.starts_with('%'))
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    if first.starts_with("*vertices") || first.starts_with("*network") {
+        Format::Pajek
+    } else if lines.any(|line| {
+        line.strip_prefix("graph")
+            .is_some_and(|rest| rest.trim_start().starts_with('['))
+    }) {
+        Format::Gml
+    } else {
+        Format::Tgf
+    }
+}
+
+/// Reads the graph from single source, all problems of TGF input are collected into
+/// [`MyError::GraphDiagnostics`], labels which can't be parsed get hint about their expected type
+fn read_source<V: TypedLabel, E: TypedLabel>(
+    source: &Source,
+    format: Option<Format>,
+) -> Result<Graph<V, E>> {
+    let mut reader = source.open()?;
+    let format = match format.or(match source {
+        Source::File(path) => detect_by_extension(path),
+        Source::Stdin => None,
+    }) {
+        Some(format) => format,
+        // only the buffered part is inspected, so the input isn't consumed
+        None => detect_by_content(&String::from_utf8_lossy(reader.fill_buf()?)),
+    };
+    // TGF is parsed line by line, other formats need the whole input
+    let read_content = |mut reader: Box<dyn BufRead>| -> io::Result<String> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+        Ok(content)
+    };
+
+    let parse_label_error = |error| match error {
+        ParseGraphError::ParseLabel(line) => {
+            MyError::ParseLabel(source.name(), line, V::NAME, E::NAME)
+        }
+        error => MyError::GraphParse(error),
+    };
+    match format {
+        Format::Tgf => {
+            TgfReader::new(reader)
+                .read_graph_diagnostics()
+                .map_err(|mut diagnostics| {
+                    for diagnostic in &mut diagnostics {
+                        if let ParseGraphError::ParseLabel(_) = diagnostic.error {
+                            diagnostic.hint = match diagnostic.is_edge {
+                                true => Some(E::HINT),
+                                false => Some(V::HINT),
+                            };
+                        }
+                    }
+                    MyError::GraphDiagnostics(source.name(), diagnostics)
+                })
+        }
+        Format::Gml => Graph::from_gml(&read_content(reader)?).map_err(parse_label_error),
+        Format::Pajek => Graph::from_pajek(&read_content(reader)?).map_err(parse_label_error),
+        format => Err(MyError::UnreadableFormat(format)),
+    }
+}
+
+/// Options of reading the graph which are shared by all commands
+pub struct Input {
+    /// format of all files, detected for every file separately if it's not specified
+    pub format: Option<Format>,
+    /// policy for vertices found in several files
+    pub on_conflict: DuplicateVertexPolicy,
+}
+
+impl Input {
+    /// Reads graphs from all files and merges them into one, no files means standard input
+    pub fn read_graph<V: TypedLabel, E: TypedLabel>(
+        &self,
+        files: &[PathBuf],
+    ) -> Result<Graph<V, E>> {
+        let stdin = [PathBuf::from("-")];
+        let files = match files.is_empty() {
+            true => &stdin[..],
+            false => files,
+        };
+
+        let mut graph = Graph::new();
+        for file in files {
+            let source = Source::new(file);
+            let other = read_source(&source, self.format)?;
+            graph
+                .merge(&other, self.on_conflict)
+                .map_err(|_| MyError::MergeConflict(source.name()))?;
+        }
+        Ok(graph)
+    }
+}
@@ -0,0 +1,89 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::num::ParseFloatError;
+use std::str::FromStr;
+
+use simple_graph::Label;
+
+use crate::*;
+
+/// `f64` which can be used as a label, values are compared with [`f64::total_cmp`]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Float(pub f64);
+
+impl PartialEq for Float {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Float {}
+
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Float {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Hash for Float {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+impl FromStr for Float {
+    type Err = ParseFloatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Float)
+    }
+}
+
+impl fmt::Display for Float {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Label type which can be selected with `--vertex-type` and `--edge-type`
+pub trait TypedLabel: Label {
+    /// name of the type in command line options
+    const NAME: &'static str;
+    /// hint for the labels which can't be parsed
+    const HINT: &'static str;
+}
+
+impl TypedLabel for String {
+    const NAME: &'static str = "string";
+    const HINT: &'static str = "label can't be converted to the expected type";
+}
+
+impl TypedLabel for i64 {
+    const NAME: &'static str = "int";
+    const HINT: &'static str = "expected integer label, see --vertex-type and --edge-type";
+}
+
+impl TypedLabel for Float {
+    const NAME: &'static str = "float";
+    const HINT: &'static str = "expected number label, see --vertex-type and --edge-type";
+}
+
+impl TypedLabel for bool {
+    const NAME: &'static str = "bool";
+    const HINT: &'static str =
+        "expected `true` or `false` label, see --vertex-type and --edge-type";
+}
+
+/// Parses label entered by the user, e.g. name of the start vertex
+pub fn parse_label<T: TypedLabel>(value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| MyError::InvalidLabel(value.into(), T::NAME))
+}
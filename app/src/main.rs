@@ -1,65 +1,100 @@
-use std::fs;
-use std::str::FromStr;
-
-use simple_graph::Graph;
-
-use colored::*;
+use std::io::{self, IsTerminal};
 
 pub use args::*;
+pub use commands::*;
+pub use editor::*;
 pub use error::*;
+pub use input::*;
+pub use label::*;
+pub use output::*;
+pub use shell::*;
 
 mod args;
+mod commands;
+mod editor;
 mod error;
+mod input;
+mod label;
+mod output;
+mod shell;
 
-fn app() -> Result<()> {
-    let Arguments {
-        file,
-        algorithm,
-        start_vertex,
-    } = argh::from_env();
-
-    let content = fs::read_to_string(file)?;
-    let graph: Graph<String, String> = Graph::from_str(&content)?;
-
-    let vertex_id = graph.get_vertex_id(&start_vertex);
-    let _ = graph.get_vertex(vertex_id)?; //try to find this in graph first
-
-    println!("{}", "Vertices traversal...".to_string().bright_green());
-    for (vertex, adjacent_vertices) in graph.vertices()? {
-        println!("- {}", vertex.to_string().bright_yellow());
-        for adjacent_vertex in adjacent_vertices {
-            println!("  {}", format!("{adjacent_vertex:?}").bright_cyan());
-        }
+fn app<V: TypedLabel, E: TypedLabel>(
+    input: &Input,
+    command: Command,
+    printer: &mut Printer,
+) -> Result<()> {
+    match command {
+        Command::Traverse(arguments) => traverse::<V, E>(input, arguments, printer),
+        Command::Convert(arguments) => convert::<V, E>(input, arguments, printer),
+        Command::Stats(arguments) => stats::<V, E>(input, arguments, printer),
+        Command::Path(arguments) => path::<V, E>(input, arguments, printer),
+        Command::Validate(arguments) => validate::<V, E>(input, arguments, printer),
+        Command::Diff(arguments) => diff::<V, E>(input, arguments, printer),
+        Command::Shell(arguments) => shell::<V, E>(input, arguments),
     }
+}
 
-    println!(
-        "{}",
-        format!("Applying \"{algorithm:?}\" algorithm to this graph...").bright_green()
-    );
+/// Instantiates the graph with the edge label type selected by the user
+fn app_with_edges<V: TypedLabel>(
+    edge_type: LabelType,
+    input: &Input,
+    command: Command,
+    printer: &mut Printer,
+) -> Result<()> {
+    match edge_type {
+        LabelType::String => app::<V, String>(input, command, printer),
+        LabelType::Int => app::<V, i64>(input, command, printer),
+        LabelType::Float => app::<V, Float>(input, command, printer),
+        LabelType::Bool => app::<V, bool>(input, command, printer),
+    }
+}
 
-    let mut visited = Vec::new();
-    let visitor_fn = |vertex: &String, adjacent_vertices: Vec<(&String, &String)>| {
-        visited.push(format!(
-            "{}: {}",
-            vertex.to_string().bright_yellow(),
-            format!("{adjacent_vertices:?}").bright_cyan()
-        ));
+fn main() {
+    let Arguments {
+        output,
+        vertex_type,
+        edge_type,
+        format,
+        on_conflict,
+        command,
+    } = argh::from_env();
+    let input = Input {
+        format,
+        on_conflict,
     };
 
-    match algorithm {
-        AlgorithmType::BreadthFirstSearch => graph.bfs(vertex_id, visitor_fn),
-        AlgorithmType::DepthFirstSearch => graph.dfs(vertex_id, visitor_fn),
-    }?;
-
-    for (i, line) in (1_usize..).zip(visited) {
-        println!("{i}. {line}");
+    // escape sequences would break machine-readable output and files
+    if output != OutputFormat::Text || !io::stdout().is_terminal() {
+        colored::control::set_override(false);
     }
 
-    Ok(())
-}
+    let mut printer = Printer::new(output);
+    let result = match vertex_type {
+        LabelType::String => app_with_edges::<String>(edge_type, &input, command, &mut printer),
+        LabelType::Int => app_with_edges::<i64>(edge_type, &input, command, &mut printer),
+        LabelType::Float => app_with_edges::<Float>(edge_type, &input, command, &mut printer),
+        LabelType::Bool => app_with_edges::<bool>(edge_type, &input, command, &mut printer),
+    };
+    if let Err(err) = &result {
+        match printer.is_text() {
+            true => {
+                if let MyError::GraphDiagnostics(file, diagnostics) = err {
+                    for diagnostic in diagnostics {
+                        eprintln!("{}", diagnostic.report(file));
+                    }
+                }
+                eprintln!("Error: {err}");
+            }
+            false => {
+                for record in err.to_json() {
+                    printer.record(record);
+                }
+            }
+        }
+    }
+    printer.finish();
 
-fn main() {
-    if let Err(err) = app() {
-        println!("Error: {err}");
+    if result.is_err() {
+        std::process::exit(1);
     }
 }
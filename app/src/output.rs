@@ -0,0 +1,134 @@
+use std::fmt;
+
+use simple_graph::escape_json;
+
+use crate::OutputFormat;
+
+/// Minimal JSON value which is enough for records printed by commands
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(&'static str, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Creates JSON object with the fields in the specified order
+    pub fn object<const N: usize>(fields: [(&'static str, JsonValue); N]) -> Self {
+        JsonValue::Object(fields.into())
+    }
+}
+
+impl From<bool> for JsonValue {
+    fn from(value: bool) -> Self {
+        JsonValue::Bool(value)
+    }
+}
+
+impl From<usize> for JsonValue {
+    fn from(value: usize) -> Self {
+        JsonValue::Number(value as f64)
+    }
+}
+
+impl From<f64> for JsonValue {
+    fn from(value: f64) -> Self {
+        JsonValue::Number(value)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(value: &str) -> Self {
+        JsonValue::String(value.into())
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(value: String) -> Self {
+        JsonValue::String(value)
+    }
+}
+
+impl<T: Into<JsonValue>> From<Option<T>> for JsonValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(JsonValue::Null, Into::into)
+    }
+}
+
+impl<T: Into<JsonValue>> FromIterator<T> for JsonValue {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        JsonValue::Array(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonValue::Null => f.write_str("null"),
+            JsonValue::Bool(value) => write!(f, "{value}"),
+            JsonValue::Number(value) if value.is_finite() => write!(f, "{value}"),
+            JsonValue::Number(_) => f.write_str("null"),
+            JsonValue::String(value) => f.write_str(&escape_json(value)),
+            JsonValue::Array(values) => {
+                f.write_str("[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_str("]")
+            }
+            JsonValue::Object(fields) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    f.write_str(&escape_json(key))?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+/// Collects records printed by commands, NDJSON records are printed immediately
+/// and JSON records are printed as a single array in [`Printer::finish`]
+pub struct Printer {
+    format: OutputFormat,
+    records: Vec<JsonValue>,
+}
+
+impl Printer {
+    pub fn new(format: OutputFormat) -> Self {
+        Self {
+            format,
+            records: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if output is intended for humans
+    pub fn is_text(&self) -> bool {
+        self.format == OutputFormat::Text
+    }
+
+    /// Prints record in machine-readable modes, it's ignored in text mode
+    pub fn record(&mut self, record: JsonValue) {
+        match self.format {
+            OutputFormat::Text => {}
+            OutputFormat::Json => self.records.push(record),
+            OutputFormat::Ndjson => println!("{record}"),
+        }
+    }
+
+    pub fn finish(self) {
+        if self.format == OutputFormat::Json {
+            println!("{}", JsonValue::Array(self.records));
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use std::fs;
+use std::path::PathBuf;
+
+use simple_graph::{BoxDrawing, TransactionalGraph, VertexId};
+
+use colored::*;
+
+use crate::*;
+
+/// Commands of the shell with their arguments and descriptions
+const COMMANDS: [(&str, &str, &str); 14] = [
+    ("add-vertex", "<label>", "adds the vertex"),
+    (
+        "add-edge",
+        "<from> <to> [label]",
+        "adds the edge between two vertices",
+    ),
+    (
+        "rm",
+        "<vertex> | <from> <to>",
+        "removes the vertex with its edges or the edge",
+    ),
+    (
+        "neighbors",
+        "<vertex>",
+        "prints adjacent vertices and labels of the edges",
+    ),
+    (
+        "bfs",
+        "<vertex>",
+        "applies breadth-first search from the vertex",
+    ),
+    (
+        "dfs",
+        "<vertex>",
+        "applies depth-first search from the vertex",
+    ),
+    (
+        "path",
+        "<from> <to>",
+        "finds the shortest path between two vertices",
+    ),
+    ("show", "", "draws the graph"),
+    (
+        "save",
+        "[file]",
+        "writes the graph in TGF, to the loaded file by default",
+    ),
+    ("undo", "", "reverts the last change of the graph"),
+    ("redo", "", "applies the last reverted change again"),
+    ("history", "", "prints entered commands"),
+    ("help", "", "prints this message"),
+    ("quit", "", "exits the shell, Ctrl-D does the same"),
+];
+
+struct Shell<V: TypedLabel, E: TypedLabel> {
+    /// the graph with history of changes for `undo` and `redo`
+    graph: TransactionalGraph<V, E>,
+    /// TGF file which is used by `save` without arguments
+    file: Option<PathBuf>,
+}
+
+impl<V: TypedLabel, E: TypedLabel> Shell<V, E> {
+    fn vertex(&self, label: &str) -> Result<VertexId> {
+        let graph = self.graph.graph();
+        let vertex_id = graph.get_vertex_id(&parse_label(label)?);
+        graph.get_vertex(vertex_id)?;
+        Ok(vertex_id)
+    }
+
+    fn search(&self, command: &str, vertex: &str) -> Result<()> {
+        let vertex_id = self.vertex(vertex)?;
+        let mut i = 0;
+        let visitor_fn = |vertex: &V, adjacent_vertices: Vec<(&V, &E)>| {
+            i += 1;
+            let vertex = vertex.to_string().bright_yellow();
+            match adjacent_vertices.is_empty() {
+                true => println!("{i}. {vertex}"),
+                false => println!(
+                    "{i}. {vertex}: {}",
+                    neighbours_text(&adjacent_vertices).bright_cyan()
+                ),
+            }
+        };
+        match command {
+            "bfs" => self.graph.graph().bfs(vertex_id, visitor_fn)?,
+            _ => self.graph.graph().dfs(vertex_id, visitor_fn)?,
+        }
+        Ok(())
+    }
+
+    fn run(&mut self, command: &str, arguments: &[String]) -> Result<()> {
+        match (command, arguments) {
+            ("add-vertex", [label]) => {
+                let label = parse_label(label)?;
+                self.graph.add_vertex(label)?;
+            }
+            ("add-edge", [from, to, label @ ..]) if label.len() <= 1 => {
+                let (from, to) = (self.vertex(from)?, self.vertex(to)?);
+                let label = match label.first() {
+                    Some(label) => parse_label(label)?,
+                    None => E::default(),
+                };
+                self.graph.add_edge(from, to, label)?;
+            }
+            ("rm", [vertex]) => {
+                let vertex = self.vertex(vertex)?;
+                self.graph.remove_vertex(vertex)?;
+            }
+            ("rm", [from, to]) => {
+                let (from, to) = (self.vertex(from)?, self.vertex(to)?);
+                self.graph.remove_edge(from, to)?;
+            }
+            ("neighbors", [vertex]) => {
+                let (_, adjacent_vertices) =
+                    self.graph.graph().get_vertex_info(self.vertex(vertex)?)?;
+                for (vertex, edge) in adjacent_vertices {
+                    let (vertex, edge) = (vertex.to_string(), edge.to_string());
+                    match edge.is_empty() {
+                        true => println!("{}", vertex.bright_yellow()),
+                        false => println!("{} ({})", vertex.bright_yellow(), edge.bright_cyan()),
+                    }
+                }
+            }
+            ("bfs" | "dfs", [vertex]) => self.search(command, vertex)?,
+            ("path", [from, to]) => {
+                let (distance, path, weighted) = find_path(self.graph.graph(), from, to)?;
+                print_path(distance, &path, weighted);
+            }
+            ("show", []) => print!("{}", BoxDrawing::new(self.graph.graph())),
+            ("save", [file]) => {
+                fs::write(file, self.graph.graph().to_string())?;
+                println!("{} {file}", "saved:".bright_green());
+            }
+            ("save", []) => {
+                let file = self.file.as_ref().ok_or_else(|| {
+                    MyError::Shell("the graph wasn't loaded from TGF file, specify the file".into())
+                })?;
+                fs::write(file, self.graph.graph().to_string())?;
+                println!("{} {}", "saved:".bright_green(), file.display());
+            }
+            ("undo", []) => self.graph.undo()?,
+            ("redo", []) => self.graph.redo()?,
+            ("help", []) => {
+                for (name, arguments, description) in COMMANDS {
+                    let usage = format!("{name} {arguments}");
+                    println!("{}{description}", format!("{usage:<30}").bright_green());
+                }
+            }
+            (command, _) => {
+                return Err(MyError::Shell(
+                    match COMMANDS.iter().find(|(name, ..)| *name == command) {
+                        Some((name, arguments, _)) => format!("usage: {name} {arguments}"),
+                        None => format!(
+                            "unknown command {command:?}, type `help` for the list of commands"
+                        ),
+                    },
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Loads the graph and runs commands entered by the user until `quit` or the end of input
+pub fn shell<V: TypedLabel, E: TypedLabel>(input: &Input, arguments: ShellArguments) -> Result<()> {
+    let ShellArguments { files } = arguments;
+    if files.is_empty() || files.iter().any(|file| file.as_os_str() == "-") {
+        return Err(MyError::Shell(
+            "commands are read from stdin, so the graph must be loaded from files".into(),
+        ));
+    }
+    let graph = input.read_graph::<V, E>(&files)?;
+
+    // `save` doesn't overwrite files in other formats by default
+    let file = match files.as_slice() {
+        [file] => Some(file.clone()),
+        _ => None,
+    }
+    .filter(|file| {
+        input.format == Some(Format::Tgf) || file.extension().is_some_and(|ext| ext == "tgf")
+    });
+    let mut shell = Shell {
+        graph: TransactionalGraph::new(graph),
+        file,
+    };
+    let mut editor = Editor::new();
+
+    println!(
+        "{}",
+        format!(
+            "Loaded {} vertices and {} edges, type `help` for the list of commands",
+            shell.graph.graph().vertices_count(),
+            shell.graph.graph().edges_count()
+        )
+        .bright_green()
+    );
+
+    loop {
+        let graph = shell.graph.graph();
+        let complete = |index, _: &str| match index {
+            0 => COMMANDS.iter().map(|(name, ..)| name.to_string()).collect(),
+            _ => graph
+                .vertices()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(vertex, _)| vertex.to_string())
+                .collect(),
+        };
+        let Some(line) = editor.read_line("graph> ", complete)? else {
+            break;
+        };
+
+        let words = split_words(&line);
+        let result = match words.split_first() {
+            None => Ok(()),
+            Some((command, _)) if command == "quit" || command == "exit" => break,
+            Some((command, _)) if command == "history" => {
+                for (i, line) in (1_usize..).zip(editor.history()) {
+                    println!("{i:>4}  {line}");
+                }
+                Ok(())
+            }
+            Some((command, arguments)) => shell.run(command, arguments),
+        };
+        if let Err(err) = result {
+            eprintln!("{} {err}", "Error:".bright_red());
+        }
+    }
+    Ok(())
+}
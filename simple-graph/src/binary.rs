@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use super::{DecodeGraphError, Graph, Label, VertexId};
+
+/// Magic bytes at the beginning of the binary graph
+const MAGIC: &[u8; 4] = b"SGRF";
+/// Current version of the binary format
+const VERSION: u8 = 1;
+/// Size of magic bytes, version and flags
+const HEADER_LEN: usize = MAGIC.len() + 2;
+/// Size of CRC-32 checksum at the end of the binary graph
+const CHECKSUM_LEN: usize = 4;
+
+/// Trait used for serialization and deserialization of the labels in compact binary format,
+/// see [`Graph::to_binary`]
+///
+/// ```
+/// use simple_graph::BinaryLabel;
+///
+/// let mut buffer = Vec::new();
+/// "Moscow".to_string().encode(&mut buffer);
+/// 180_u32.encode(&mut buffer);
+///
+/// let mut input = buffer.as_slice();
+/// assert_eq!(String::decode(&mut input), Some("Moscow".into()));
+/// assert_eq!(u32::decode(&mut input), Some(180));
+/// assert!(input.is_empty());
+/// ```
+pub trait BinaryLabel: Sized {
+    /// Appends encoded value to the `buffer`
+    fn encode(&self, buffer: &mut Vec<u8>);
+    /// Decodes value from the beginning of the `input` and advances it,
+    /// returns `None` if input is truncated or malformed
+    fn decode(input: &mut &[u8]) -> Option<Self>;
+}
+
+fn encode_varint(mut value: u64, buffer: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buffer.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+fn decode_varint(input: &mut &[u8]) -> Option<u64> {
+    let mut value = 0_u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = input.split_first()?;
+        *input = rest;
+        value |= u64::from(byte & 0x7f).checked_shl(shift)?;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+macro_rules! impl_unsigned {
+    ($($ty:ty),*) => {$(
+        impl BinaryLabel for $ty {
+            fn encode(&self, buffer: &mut Vec<u8>) {
+                encode_varint(*self as u64, buffer);
+            }
+
+            fn decode(input: &mut &[u8]) -> Option<Self> {
+                decode_varint(input)?.try_into().ok()
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_signed {
+    ($($ty:ty),*) => {$(
+        impl BinaryLabel for $ty {
+            fn encode(&self, buffer: &mut Vec<u8>) {
+                // zigzag encoding keeps small negative numbers short
+                let value = *self as i64;
+                encode_varint(((value << 1) ^ (value >> 63)) as u64, buffer);
+            }
+
+            fn decode(input: &mut &[u8]) -> Option<Self> {
+                let value = decode_varint(input)?;
+                (((value >> 1) as i64) ^ -((value & 1) as i64)).try_into().ok()
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64, usize);
+impl_signed!(i8, i16, i32, i64, isize);
+
+impl BinaryLabel for bool {
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        buffer.push(u8::from(*self));
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        match u8::decode(input)? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl BinaryLabel for char {
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        u32::from(*self).encode(buffer);
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        char::from_u32(u32::decode(input)?)
+    }
+}
+
+impl BinaryLabel for String {
+    fn encode(&self, buffer: &mut Vec<u8>) {
+        self.len().encode(buffer);
+        buffer.extend_from_slice(self.as_bytes());
+    }
+
+    fn decode(input: &mut &[u8]) -> Option<Self> {
+        let len = usize::decode(input)?;
+        if input.len() < len {
+            return None;
+        }
+        let (bytes, rest) = input.split_at(len);
+        *input = rest;
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+/// Lookup table for CRC-32 (IEEE 802.3) checksum
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0_u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut crc = n as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                0xedb8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[n] = crc;
+        n += 1;
+    }
+    table
+};
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0_u32, |crc, &byte| {
+        CRC32_TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+impl<V: Label + BinaryLabel, E: Label + BinaryLabel> Graph<V, E> {
+    /// Serializes graph into compact binary format
+    ///
+    /// Format consists of magic bytes `SGRF`, version, table of the vertex labels,
+    /// adjacency lists with varint-encoded vertex indices and CRC-32 checksum of all previous bytes
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> =
+    ///     Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    ///
+    /// let bytes = graph.to_binary();
+    /// assert!(bytes.starts_with(b"SGRF"));
+    /// assert_eq!(Graph::from_binary(&bytes), Ok(graph));
+    /// ```
+    pub fn to_binary(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(MAGIC);
+        buffer.push(VERSION);
+        buffer.push(0); // flags, reserved for future versions
+
+        let indices = self
+            .vertices
+            .keys()
+            .enumerate()
+            .map(|(index, &vertex_id)| (vertex_id, index))
+            .collect::<HashMap<VertexId, usize>>();
+
+        self.vertices_count().encode(&mut buffer);
+        for vertex_id in self.vertices.keys() {
+            if let Some(vertex) = self.vertices_data.get(vertex_id) {
+                vertex.encode(&mut buffer);
+            }
+        }
+
+        for neighbours in self.vertices.values() {
+            neighbours.len().encode(&mut buffer);
+            for ([_, to], edge) in neighbours {
+                indices[to].encode(&mut buffer);
+                edge.encode(&mut buffer);
+            }
+        }
+
+        let checksum = crc32(&buffer);
+        buffer.extend_from_slice(&checksum.to_le_bytes());
+        buffer
+    }
+
+    /// Writes graph in compact binary format to the [`Write`], see [`Graph::to_binary`]
+    pub fn write_binary<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&self.to_binary())?;
+        writer.flush()
+    }
+
+    /// Deserializes graph from compact binary format, see [`Graph::to_binary`]
+    ///
+    /// Returns [`DecodeGraphError`] if the input is truncated, corrupted or has unsupported version
+    ///
+    /// ```
+    /// use simple_graph::{DecodeGraphError, Graph};
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> =
+    ///     Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let mut bytes = graph.to_binary();
+    ///
+    /// let truncated = &bytes[..bytes.len() / 2];
+    /// assert!(matches!(
+    ///     Graph::<String, u32>::from_binary(truncated),
+    ///     Err(DecodeGraphError::ChecksumMismatch(..))
+    /// ));
+    ///
+    /// bytes[10] ^= 0xff;
+    /// assert!(matches!(
+    ///     Graph::<String, u32>::from_binary(&bytes),
+    ///     Err(DecodeGraphError::ChecksumMismatch(..))
+    /// ));
+    ///
+    /// assert_eq!(
+    ///     Graph::<String, u32>::from_binary(b"TGF"),
+    ///     Err(DecodeGraphError::UnexpectedEnd(3))
+    /// );
+    /// ```
+    pub fn from_binary(bytes: &[u8]) -> Result<Self, DecodeGraphError> {
+        if bytes.len() < HEADER_LEN + CHECKSUM_LEN {
+            return Err(DecodeGraphError::UnexpectedEnd(bytes.len()));
+        }
+        if !bytes.starts_with(MAGIC) {
+            return Err(DecodeGraphError::InvalidMagic);
+        }
+        if bytes[MAGIC.len()] != VERSION {
+            return Err(DecodeGraphError::UnsupportedVersion(bytes[MAGIC.len()]));
+        }
+
+        let (content, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+        let expected = u32::from_le_bytes(checksum.try_into().expect("checksum has 4 bytes"));
+        let found = crc32(content);
+        if expected != found {
+            return Err(DecodeGraphError::ChecksumMismatch(expected, found));
+        }
+
+        let mut input = &content[HEADER_LEN..];
+        let offset = |input: &[u8]| content.len() - input.len();
+
+        let count =
+            usize::decode(&mut input).ok_or(DecodeGraphError::UnexpectedEnd(offset(input)))?;
+        // every vertex takes at least one byte, so huge count can't allocate too much memory
+        let mut graph = Self::with_capacity(count.min(input.len()));
+
+        let mut ids = Vec::with_capacity(count.min(input.len()));
+        for _ in 0..count {
+            let position = offset(input);
+            let vertex = V::decode(&mut input).ok_or(DecodeGraphError::InvalidLabel(position))?;
+            ids.push(
+                graph
+                    .add_vertex(vertex)
+                    .map_err(DecodeGraphError::GraphError)?,
+            );
+        }
+
+        for &from in &ids {
+            let degree =
+                usize::decode(&mut input).ok_or(DecodeGraphError::UnexpectedEnd(offset(input)))?;
+            for _ in 0..degree {
+                let position = offset(input);
+                let to = usize::decode(&mut input)
+                    .and_then(|index| ids.get(index))
+                    .ok_or(DecodeGraphError::InvalidVertexIndex(position))?;
+                let position = offset(input);
+                let edge = E::decode(&mut input).ok_or(DecodeGraphError::InvalidLabel(position))?;
+                graph
+                    .add_edge(from, *to, edge)
+                    .map_err(DecodeGraphError::GraphError)?;
+            }
+        }
+
+        if !input.is_empty() {
+            return Err(DecodeGraphError::TrailingBytes(offset(input)));
+        }
+
+        Ok(graph)
+    }
+}
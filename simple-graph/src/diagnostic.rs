@@ -0,0 +1,218 @@
+use std::ops::Range;
+
+use super::{GraphOperationError, ParseGraphError};
+
+/// [`ParseGraphError`] with location of the problem in the source text
+///
+/// ```
+/// use simple_graph::{Graph, ParseGraphError, TgfDiagnostic};
+/// use std::str::FromStr;
+///
+/// let s = "1 Moscow\n2x Vladimir\n";
+/// let error = Graph::<String, u32>::from_str(s).unwrap_err();
+/// assert_eq!(error, ParseGraphError::ParseInt(2));
+///
+/// let diagnostic = TgfDiagnostic::from_source(s, error);
+/// assert_eq!(diagnostic.line, 2);
+/// assert_eq!(diagnostic.column, 1);
+/// assert_eq!(&s[diagnostic.span.clone()], "2x");
+/// assert_eq!(diagnostic.snippet, "2x Vladimir");
+/// assert!(!diagnostic.is_edge);
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TgfDiagnostic {
+    /// error which happened during parsing
+    pub error: ParseGraphError,
+    /// line number, starting from 1
+    pub line: usize,
+    /// column number of the span start in characters, starting from 1
+    pub column: usize,
+    /// byte range of the offending text in the whole input
+    pub span: Range<usize>,
+    /// text of the offending line without line ending
+    pub snippet: String,
+    /// suggestion how to fix the error
+    pub hint: Option<&'static str>,
+    /// `true` if the line is an edge definition located after the `#` separator
+    pub is_edge: bool,
+}
+
+impl TgfDiagnostic {
+    /// Creates diagnostic for the error which happened in `line` starting at byte `offset` of the input,
+    /// `is_edge` tells whether the line is located after the `#` separator
+    pub(crate) fn new(error: ParseGraphError, line: &str, offset: usize, is_edge: bool) -> Self {
+        let line = line.trim_end_matches(['\n', '\r']);
+        let range = error_range(&error, line, is_edge);
+        Self {
+            line: error.line(),
+            column: line[..range.start].chars().count() + 1,
+            span: offset + range.start..offset + range.end,
+            snippet: line.into(),
+            hint: hint(&error),
+            error,
+            is_edge,
+        }
+    }
+
+    /// Creates diagnostic for the error returned after parsing the whole `source`,
+    /// for example by [`impl<V, E> FromStr for Graph<V, E>`](crate::Graph#impl-FromStr-for-Graph<V,+E>)
+    pub fn from_source(source: &str, error: ParseGraphError) -> Self {
+        let mut offset = 0;
+        let mut is_edge = false;
+        for (n, line) in (1_usize..).zip(source.split_inclusive('\n')) {
+            if n == error.line() {
+                return Self::new(error, line, offset, is_edge);
+            }
+            is_edge |= line.starts_with('#');
+            offset += line.len();
+        }
+        Self::new(error, "", offset, is_edge)
+    }
+
+    /// Returns renderer which shows the diagnostic with annotated source excerpt
+    ///
+    /// ```
+    /// use simple_graph::{Graph, TgfDiagnostic};
+    /// use std::str::FromStr;
+    ///
+    /// let s = "1 Moscow\n#\n1 2 180\n";
+    /// let error = Graph::<String, u32>::from_str(s).unwrap_err();
+    ///
+    /// let report = TgfDiagnostic::from_source(s, error).report("moscow.tgf").to_string();
+    /// let expected = concat!(
+    ///     "error: failed to join vertices with ids 1, 2 because they are not defined at line 3\n",
+    ///     " --> moscow.tgf:3:1\n",
+    ///     "  |\n",
+    ///     "3 | 1 2 180\n",
+    ///     "  | ^^^\n",
+    ///     "  = hint: define both vertices before the `#` separator\n",
+    /// );
+    /// assert_eq!(report, expected);
+    /// ```
+    #[cfg(feature = "pretty")]
+    pub fn report<'a>(&'a self, name: &'a str) -> TgfReport<'a> {
+        TgfReport {
+            diagnostic: self,
+            name,
+        }
+    }
+}
+
+/// Pretty renderer of [`TgfDiagnostic`], see [`TgfDiagnostic::report`]
+#[cfg(feature = "pretty")]
+pub struct TgfReport<'a> {
+    diagnostic: &'a TgfDiagnostic,
+    name: &'a str,
+}
+
+#[cfg(feature = "pretty")]
+impl std::fmt::Display for TgfReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let TgfDiagnostic {
+            error,
+            line,
+            column,
+            span,
+            snippet,
+            hint,
+            ..
+        } = self.diagnostic;
+
+        let number = line.to_string();
+        let gutter = " ".repeat(number.len());
+        let start = snippet
+            .char_indices()
+            .nth(column - 1)
+            .map_or(snippet.len(), |(start, _)| start);
+        let end = (start + span.len()).min(snippet.len());
+        let underline = snippet[start..end].chars().count().max(1);
+
+        writeln!(f, "error: {error}")?;
+        writeln!(f, "{gutter}--> {}:{line}:{column}", self.name)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{number} | {snippet}")?;
+        writeln!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(column - 1),
+            "^".repeat(underline)
+        )?;
+        if let Some(hint) = hint {
+            writeln!(f, "{gutter} = hint: {hint}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns byte range which covers `tokens` of the `line`, tokens are separated by whitespace
+fn token_range(line: &str, tokens: Range<usize>) -> Option<Range<usize>> {
+    let mut ranges = line
+        .split_whitespace()
+        .map(|token| {
+            let start = token.as_ptr() as usize - line.as_ptr() as usize;
+            start..start + token.len()
+        })
+        .skip(tokens.start)
+        .take(tokens.len());
+    let first = ranges.next()?;
+    let last = ranges.last().unwrap_or(first.clone());
+    Some(first.start..last.end)
+}
+
+/// Returns byte range of the label which follows `tokens` leading tokens
+fn label_range(line: &str, tokens: usize) -> Range<usize> {
+    let start = token_range(line, tokens - 1..tokens).map_or(0, |range| range.end);
+    let label = line[start..].trim();
+    match label.is_empty() {
+        true => line.len()..line.len(),
+        false => {
+            let start = label.as_ptr() as usize - line.as_ptr() as usize;
+            start..start + label.len()
+        }
+    }
+}
+
+fn error_range(error: &ParseGraphError, line: &str, is_edge: bool) -> Range<usize> {
+    let whole = 0..line.len();
+    let label_tokens = if is_edge { 2 } else { 1 };
+    match error {
+        ParseGraphError::VertexDefinition(_)
+        | ParseGraphError::EdgeDefinition(_)
+        | ParseGraphError::Syntax(..)
+        | ParseGraphError::Io(..) => whole,
+        ParseGraphError::ParseInt(_) => line
+            .split_whitespace()
+            .take(label_tokens)
+            .position(|token| token.parse::<usize>().is_err())
+            .and_then(|n| token_range(line, n..n + 1))
+            .unwrap_or(whole),
+        ParseGraphError::VertexAlreadyDefined(..) => token_range(line, 0..1).unwrap_or(whole),
+        ParseGraphError::VerticesNotDefined(..) => token_range(line, 0..2).unwrap_or(whole),
+        ParseGraphError::ParseLabel(_) | ParseGraphError::GraphError(..) => {
+            label_range(line, label_tokens)
+        }
+    }
+}
+
+fn hint(error: &ParseGraphError) -> Option<&'static str> {
+    match error {
+        ParseGraphError::VertexDefinition(_) => {
+            Some("vertex definition looks like `<index> <label>`")
+        }
+        ParseGraphError::EdgeDefinition(_) => {
+            Some("edge definition looks like `<from> <to> <label>`")
+        }
+        ParseGraphError::VertexAlreadyDefined(..) => Some("every vertex must have unique index"),
+        ParseGraphError::VerticesNotDefined(..) => {
+            Some("define both vertices before the `#` separator")
+        }
+        ParseGraphError::ParseInt(_) => Some("index must be a non-negative integer"),
+        ParseGraphError::ParseLabel(_) => Some("label can't be converted to the expected type"),
+        ParseGraphError::GraphError(GraphOperationError::VertexAlreadyExists, _) => {
+            Some("vertex with the same label is already defined")
+        }
+        ParseGraphError::Syntax(..) | ParseGraphError::GraphError(..) | ParseGraphError::Io(..) => {
+            None
+        }
+    }
+}
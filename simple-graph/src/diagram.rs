@@ -0,0 +1,217 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+
+use super::{Graph, Label, VertexId};
+
+/// Direction of the diagram, see [`Mermaid`] and [`PlantUml`]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum Direction {
+    /// from top to bottom, `TD`
+    #[default]
+    TopDown,
+    /// from bottom to top, `BT`
+    BottomUp,
+    /// from left to right, `LR`
+    LeftRight,
+    /// from right to left, `RL`
+    RightLeft,
+}
+
+impl Direction {
+    fn as_mermaid(self) -> &'static str {
+        match self {
+            Direction::TopDown => "TD",
+            Direction::BottomUp => "BT",
+            Direction::LeftRight => "LR",
+            Direction::RightLeft => "RL",
+        }
+    }
+}
+
+/// Assigns unique identifiers which contain only ASCII letters, digits and `_` to the vertices
+///
+/// Labels like `Second node` become `Second_node`, collisions get numeric suffix
+fn identifiers<V: Label, E: Label>(
+    graph: &Graph<V, E>,
+) -> Result<Vec<(VertexId, String, &V)>, fmt::Error> {
+    let mut used = HashSet::new();
+    let mut identifiers = Vec::with_capacity(graph.vertices_count());
+    for &vertex_id in graph.vertices.keys() {
+        let vertex = graph.get_vertex(vertex_id).map_err(|_| fmt::Error)?;
+        let mut base = vertex
+            .to_string()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>();
+        if base.is_empty() || base.starts_with(|c: char| c.is_ascii_digit()) {
+            base.insert_str(0, "v_");
+        }
+        // `end` breaks flowcharts in Mermaid
+        if base.eq_ignore_ascii_case("end") {
+            base.push('_');
+        }
+
+        let mut identifier = base.clone();
+        for n in 2_usize.. {
+            if used.insert(identifier.clone()) {
+                break;
+            }
+            identifier = format!("{base}_{n}");
+        }
+        identifiers.push((vertex_id, identifier, vertex));
+    }
+    Ok(identifiers)
+}
+
+/// Writes edges using identifiers of the vertices, `write_edge` receives `(from, to, label)`
+fn write_edges<V: Label, E: Label>(
+    f: &mut Formatter<'_>,
+    graph: &Graph<V, E>,
+    identifiers: &[(VertexId, String, &V)],
+    write_edge: impl Fn(&mut Formatter<'_>, &str, &str, String) -> fmt::Result,
+) -> fmt::Result {
+    let names = identifiers
+        .iter()
+        .map(|(vertex_id, identifier, _)| (*vertex_id, identifier.as_str()))
+        .collect::<HashMap<_, _>>();
+    for neighbours in graph.vertices.values() {
+        for ([from, to], edge) in neighbours {
+            let (from, to) = names.get(from).zip(names.get(to)).ok_or(fmt::Error)?;
+            write_edge(f, from, to, edge.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// Formats [`Graph<V, E>`] as [Mermaid](https://mermaid.js.org) `flowchart` diagram
+///
+/// ```
+/// use simple_graph::{Direction, Graph, Mermaid};
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, String> =
+///     Graph::from_str(include_str!("../test_input/default.tgf")).unwrap();
+///
+/// let expected = concat!(
+///     "flowchart LR\n",
+///     "    First_node[\"First node\"]\n",
+///     "    Second_node[\"Second node\"]\n",
+///     "    First_node -->|\"Edge between the two\"| Second_node\n",
+/// );
+/// assert_eq!(Mermaid::new(&graph).direction(Direction::LeftRight).to_string(), expected);
+/// ```
+pub struct Mermaid<'a, V: Label, E: Label> {
+    graph: &'a Graph<V, E>,
+    direction: Direction,
+}
+
+impl<'a, V: Label, E: Label> Mermaid<'a, V, E> {
+    /// Creates Mermaid formatter with [`Direction::TopDown`] direction
+    pub fn new(graph: &'a Graph<V, E>) -> Self {
+        Self {
+            graph,
+            direction: Direction::default(),
+        }
+    }
+
+    /// Sets direction of the flowchart
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+}
+
+/// Escapes text which is written inside of the double quotes in Mermaid
+fn escape_mermaid(s: &str) -> String {
+    s.replace('"', "#quot;")
+}
+
+impl<V: Label, E: Label> Display for Mermaid<'_, V, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let identifiers = identifiers(self.graph)?;
+
+        writeln!(f, "flowchart {}", self.direction.as_mermaid())?;
+        for (_, identifier, vertex) in &identifiers {
+            let text = escape_mermaid(&vertex.to_string());
+            writeln!(f, "    {identifier}[\"{text}\"]")?;
+        }
+        write_edges(
+            f,
+            self.graph,
+            &identifiers,
+            |f, from, to, edge| match edge.is_empty() {
+                true => writeln!(f, "    {from} --> {to}"),
+                false => writeln!(f, "    {from} -->|\"{}\"| {to}", escape_mermaid(&edge)),
+            },
+        )
+    }
+}
+
+/// Formats [`Graph<V, E>`] as [PlantUML](https://plantuml.com) diagram where vertices are rectangles
+///
+/// PlantUML supports only `top to bottom` and `left to right` directions,
+/// so [`Direction::BottomUp`] and [`Direction::RightLeft`] are written as the closest of them
+///
+/// ```
+/// use simple_graph::{Direction, Graph, PlantUml};
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, String> =
+///     Graph::from_str(include_str!("../test_input/default.tgf")).unwrap();
+///
+/// let expected = concat!(
+///     "@startuml\n",
+///     "left to right direction\n",
+///     "rectangle \"First node\" as First_node\n",
+///     "rectangle \"Second node\" as Second_node\n",
+///     "First_node --> Second_node : Edge between the two\n",
+///     "@enduml\n",
+/// );
+/// assert_eq!(PlantUml::new(&graph).direction(Direction::LeftRight).to_string(), expected);
+/// ```
+pub struct PlantUml<'a, V: Label, E: Label> {
+    graph: &'a Graph<V, E>,
+    direction: Direction,
+}
+
+impl<'a, V: Label, E: Label> PlantUml<'a, V, E> {
+    /// Creates PlantUML formatter with [`Direction::TopDown`] direction
+    pub fn new(graph: &'a Graph<V, E>) -> Self {
+        Self {
+            graph,
+            direction: Direction::default(),
+        }
+    }
+
+    /// Sets direction of the diagram
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+}
+
+impl<V: Label, E: Label> Display for PlantUml<'_, V, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let identifiers = identifiers(self.graph)?;
+
+        writeln!(f, "@startuml")?;
+        match self.direction {
+            Direction::TopDown | Direction::BottomUp => writeln!(f, "top to bottom direction")?,
+            Direction::LeftRight | Direction::RightLeft => writeln!(f, "left to right direction")?,
+        }
+        for (_, identifier, vertex) in &identifiers {
+            let text = vertex.to_string().replace('"', "'");
+            writeln!(f, "rectangle \"{text}\" as {identifier}")?;
+        }
+        write_edges(
+            f,
+            self.graph,
+            &identifiers,
+            |f, from, to, edge| match edge.is_empty() {
+                true => writeln!(f, "{from} --> {to}"),
+                false => writeln!(f, "{from} --> {to} : {edge}"),
+            },
+        )?;
+        writeln!(f, "@enduml")
+    }
+}
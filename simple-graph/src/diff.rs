@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::mem;
+
+use super::{Graph, GraphOperationError, Label, Result, VertexId};
+
+/// Changes which turn one graph into another, vertices and edges are identified by their labels,
+/// so the diff doesn't depend on the order of lines in TGF files
+///
+/// Edge between the same vertices which has exactly one label in both graphs is relabelled,
+/// otherwise different labels are removed and added
+///
+/// ### Serialization to [`String`]
+/// The diff is printed in TGF-like format: vertices, `#` separator and edges,
+/// every line starts with `+` for added, `-` for removed and `~` for relabelled items.
+/// Labels with whitespace are written in double quotes
+///
+/// ```
+/// use simple_graph::Graph;
+/// use std::str::FromStr;
+///
+/// let old: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+/// let new: Graph<String, u32> = Graph::from_str(concat!(
+///     "1 Vologda\n",
+///     "2 Moscow\n",
+///     "3 Vladimir\n",
+///     "4 Yaroslavl\n",
+///     "5 Nizhny Novgorod\n",
+///     "#\n",
+///     "2 3 190\n",
+///     "2 4 250\n",
+///     "4 1 175\n",
+///     "3 5 230\n",
+/// ))
+/// .unwrap();
+///
+/// let diff = old.diff(&new);
+/// let expected = concat!(
+///     "+ \"Nizhny Novgorod\"\n",
+///     "- Novgorod\n",
+///     "#\n",
+///     "+ Vladimir \"Nizhny Novgorod\" 230\n",
+///     "- Vladimir Novgorod 225\n",
+///     "~ Moscow Vladimir 180 -> 190\n",
+/// );
+/// assert_eq!(diff.to_string(), expected);
+///
+/// let mut patched = old.clone();
+/// patched.apply(&diff).unwrap();
+/// assert!(patched.diff(&new).is_empty());
+///
+/// patched.apply(&diff.invert()).unwrap();
+/// assert!(patched.diff(&old).is_empty());
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct GraphDiff<V: Label, E: Label> {
+    /// vertices which exist only in the new graph
+    pub added_vertices: Vec<V>,
+    /// vertices which exist only in the old graph
+    pub removed_vertices: Vec<V>,
+    /// edges `([from, to], label)` which exist only in the new graph
+    pub added_edges: Vec<([V; 2], E)>,
+    /// edges `([from, to], label)` which exist only in the old graph
+    pub removed_edges: Vec<([V; 2], E)>,
+    /// edges `([from, to], old_label, new_label)` which have different labels
+    pub relabelled_edges: Vec<([V; 2], E, E)>,
+}
+
+impl<V: Label, E: Label> GraphDiff<V, E> {
+    /// Returns `true` if graphs are equal
+    pub fn is_empty(&self) -> bool {
+        self.added_vertices.is_empty()
+            && self.removed_vertices.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.relabelled_edges.is_empty()
+    }
+
+    /// Returns the diff which reverts this one
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let old: Graph<String, u32> = Graph::from_str("1 Moscow\n2 Kazan\n#\n1 2 800\n").unwrap();
+    /// let new: Graph<String, u32> = Graph::from_str("1 Moscow\n2 Kazan\n#\n1 2 820\n").unwrap();
+    ///
+    /// assert_eq!(old.diff(&new).invert(), new.diff(&old));
+    /// ```
+    pub fn invert(&self) -> Self {
+        Self {
+            added_vertices: self.removed_vertices.clone(),
+            removed_vertices: self.added_vertices.clone(),
+            added_edges: self.removed_edges.clone(),
+            removed_edges: self.added_edges.clone(),
+            relabelled_edges: self
+                .relabelled_edges
+                .iter()
+                .map(|(vertices, old, new)| (vertices.clone(), new.clone(), old.clone()))
+                .collect(),
+        }
+    }
+}
+
+/// Labels of the edges grouped by their vertices
+fn edge_labels<V: Label, E: Label>(graph: &Graph<V, E>) -> HashMap<[VertexId; 2], Vec<&E>> {
+    let mut labels = HashMap::<_, Vec<_>>::new();
+    for neighbours in graph.vertices.values() {
+        for (vertices, edge) in neighbours {
+            labels.entry(*vertices).or_default().push(edge);
+        }
+    }
+    labels
+}
+
+impl<V: Label, E: Label> Graph<V, E> {
+    fn edge_vertices(&self, [from, to]: [VertexId; 2]) -> Result<[V; 2]> {
+        Ok([self.get_vertex(from)?.clone(), self.get_vertex(to)?.clone()])
+    }
+
+    /// Finds changes which turn this graph into `other`, see [`GraphDiff`]
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let old: Graph<String, u32> = Graph::from_str("1 Moscow\n2 Kazan\n#\n1 2 800\n").unwrap();
+    /// let new: Graph<String, u32> = Graph::from_str("1 Kazan\n2 Moscow\n#\n2 1 800\n").unwrap();
+    /// assert!(old.diff(&new).is_empty());
+    ///
+    /// let diff = new.diff(&Graph::new());
+    /// assert_eq!(diff.removed_vertices, ["Kazan", "Moscow"]);
+    /// assert_eq!(diff.removed_edges, [(["Moscow".to_string(), "Kazan".to_string()], 800)]);
+    /// ```
+    pub fn diff(&self, other: &Graph<V, E>) -> GraphDiff<V, E> {
+        // vertices keep insertion order of the graph where they exist
+        let missing = |graph: &Graph<V, E>, other: &Graph<V, E>| {
+            graph
+                .vertices
+                .keys()
+                .filter(|vertex_id| !other.vertices.contains_key(vertex_id))
+                .filter_map(|&vertex_id| graph.get_vertex(vertex_id).ok().cloned())
+                .collect()
+        };
+        let mut diff = GraphDiff {
+            added_vertices: missing(other, self),
+            removed_vertices: missing(self, other),
+            ..GraphDiff::default()
+        };
+
+        let old_labels = edge_labels(self);
+        let new_labels = edge_labels(other);
+        // edges are relabelled only if there is no doubt which label replaces which one
+        let relabelled = |vertices| match (old_labels.get(vertices), new_labels.get(vertices)) {
+            (Some(old), Some(new)) => old.len() == 1 && new.len() == 1,
+            _ => false,
+        };
+
+        for neighbours in self.vertices.values() {
+            for (vertices, edge) in neighbours {
+                let Ok(labels) = self.edge_vertices(*vertices) else {
+                    continue;
+                };
+                match new_labels.get(vertices) {
+                    Some(new) if new.contains(&edge) => {}
+                    Some(new) if relabelled(vertices) => {
+                        diff.relabelled_edges
+                            .push((labels, edge.clone(), new[0].clone()))
+                    }
+                    _ => diff.removed_edges.push((labels, edge.clone())),
+                }
+            }
+        }
+        for neighbours in other.vertices.values() {
+            for (vertices, edge) in neighbours {
+                let Ok(labels) = other.edge_vertices(*vertices) else {
+                    continue;
+                };
+                match old_labels.get(vertices) {
+                    Some(old) if old.contains(&edge) || relabelled(vertices) => {}
+                    _ => diff.added_edges.push((labels, edge.clone())),
+                }
+            }
+        }
+        diff
+    }
+
+    /// Applies changes found by [`Graph::diff`], the graph stays unchanged on error
+    ///
+    /// Returns [`GraphOperationError::VertexAlreadyExists`] if added vertex exists,
+    /// [`GraphOperationError::VertexDoesNotExist`] if removed vertex doesn't exist
+    /// and [`GraphOperationError::EdgeDoesNotExist`] if removed or relabelled edge
+    /// doesn't exist with the expected label
+    ///
+    /// ```
+    /// use simple_graph::{Graph, GraphOperationError};
+    /// use std::str::FromStr;
+    ///
+    /// let old: Graph<String, u32> = Graph::from_str("1 Moscow\n2 Kazan\n#\n1 2 800\n").unwrap();
+    /// let new: Graph<String, u32> = Graph::from_str("1 Moscow\n2 Kazan\n#\n1 2 820\n").unwrap();
+    /// let diff = old.diff(&new);
+    ///
+    /// let mut graph = new.clone();
+    /// assert_eq!(graph.apply(&diff), Err(GraphOperationError::EdgeDoesNotExist));
+    /// assert_eq!(graph, new);
+    /// ```
+    pub fn apply(&mut self, diff: &GraphDiff<V, E>) -> Result<()> {
+        // changes are checked on the copy, so observers see them only if all of them succeed
+        let mut graph = self.clone();
+        graph.apply_changes(diff)?;
+        if self.observers.is_empty() {
+            graph.observers = mem::take(&mut self.observers);
+            *self = graph;
+            return Ok(());
+        }
+        self.apply_changes(diff)
+    }
+
+    fn apply_changes(&mut self, diff: &GraphDiff<V, E>) -> Result<()> {
+        let id = |graph: &Graph<V, E>, [from, to]: &[V; 2]| {
+            [graph.get_vertex_id(from), graph.get_vertex_id(to)]
+        };
+        let remove_edge = |graph: &mut Graph<V, E>, [from, to]: [VertexId; 2], edge: &E| match graph
+            .remove_labelled_edge(from, to, edge)
+        {
+            true => Ok(()),
+            false => Err(GraphOperationError::EdgeDoesNotExist),
+        };
+
+        for (vertices, edge) in &diff.removed_edges {
+            let vertices = id(self, vertices);
+            remove_edge(self, vertices, edge)?;
+        }
+        for (vertices, old, _) in &diff.relabelled_edges {
+            let vertices = id(self, vertices);
+            remove_edge(self, vertices, old)?;
+        }
+        for vertex in &diff.removed_vertices {
+            self.remove_vertex(self.get_vertex_id(vertex))?;
+        }
+        for vertex in &diff.added_vertices {
+            self.add_vertex(vertex.clone())?;
+        }
+        for (vertices, _, new) in &diff.relabelled_edges {
+            let [from, to] = id(self, vertices);
+            self.add_edge(from, to, new.clone())?;
+        }
+        for (vertices, edge) in &diff.added_edges {
+            let [from, to] = id(self, vertices);
+            self.add_edge(from, to, edge.clone())?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the label in double quotes if it can't be read back as a single word
+fn write_label<T: Display>(f: &mut fmt::Formatter<'_>, label: &T) -> fmt::Result {
+    let label = label.to_string();
+    if !label.is_empty() && !label.contains(char::is_whitespace) && !label.starts_with('"') {
+        return f.write_str(&label);
+    }
+    f.write_str("\"")?;
+    for c in label.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    f.write_str("\"")
+}
+
+impl<V: Label, E: Label> Display for GraphDiff<V, E> {
+    /// Formats the diff as TGF-like patch, see [`GraphDiff`]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let vertices = [("+", &self.added_vertices), ("-", &self.removed_vertices)];
+        for (sign, vertices) in vertices {
+            for vertex in vertices {
+                write!(f, "{sign} ")?;
+                write_label(f, vertex)?;
+                writeln!(f)?;
+            }
+        }
+        writeln!(f, "#")?;
+
+        let edges = [("+", &self.added_edges), ("-", &self.removed_edges)];
+        for (sign, edges) in edges {
+            for ([from, to], edge) in edges {
+                write!(f, "{sign} ")?;
+                write_label(f, from)?;
+                f.write_str(" ")?;
+                write_label(f, to)?;
+                f.write_str(" ")?;
+                write_label(f, edge)?;
+                writeln!(f)?;
+            }
+        }
+        for ([from, to], old, new) in &self.relabelled_edges {
+            f.write_str("~ ")?;
+            write_label(f, from)?;
+            f.write_str(" ")?;
+            write_label(f, to)?;
+            f.write_str(" ")?;
+            write_label(f, old)?;
+            f.write_str(" -> ")?;
+            write_label(f, new)?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
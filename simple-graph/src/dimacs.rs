@@ -0,0 +1,236 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::{Graph, Label, ParseGraphError, VertexId, parse_index, parse_label};
+
+/// Graph with vertices `1..=n` labeled by their numbers, it's used by formats with integer vertex ids
+///
+/// All vertices are created upfront and their ids are kept in [`Vec`],
+/// so adding edges doesn't require hashing of the labels
+pub(crate) struct NumberedGraph<W: Label> {
+    graph: Graph<usize, W>,
+    ids: Vec<VertexId>,
+}
+
+/// The largest count of vertices in the header of numbered formats, all of them are created
+/// before edges are read, so larger counts are rejected instead of running out of memory
+pub(crate) const MAX_NUMBERED_VERTICES: usize = 1 << 24;
+
+impl<W: Label> NumberedGraph<W> {
+    /// Creates vertices `1..=vertices` declared at line `n`
+    pub(crate) fn new(vertices: usize, n: usize) -> Result<Self, ParseGraphError> {
+        if vertices > MAX_NUMBERED_VERTICES {
+            return Err(ParseGraphError::Syntax("too many vertices", n));
+        }
+        let mut graph = Graph::with_capacity(vertices);
+        let ids = graph
+            .extend_vertices(1..=vertices)
+            .expect("vertices are unique");
+        Ok(Self { graph, ids })
+    }
+
+    /// Adds edge between vertices with numbers `from` and `to` which was found at line `n`
+    pub(crate) fn add_edge(
+        &mut self,
+        from: usize,
+        to: usize,
+        weight: W,
+        n: usize,
+    ) -> Result<(), ParseGraphError> {
+        let get = |index: usize| index.checked_sub(1).and_then(|i| self.ids.get(i)).copied();
+        let (from_id, to_id) = get(from)
+            .zip(get(to))
+            .ok_or(ParseGraphError::VerticesNotDefined(from, to, n))?;
+        self.graph
+            .add_edge(from_id, to_id, weight)
+            .map_err(|err| ParseGraphError::GraphError(err, n))
+    }
+
+    pub(crate) fn finish(self) -> Graph<usize, W> {
+        self.graph
+    }
+}
+
+/// Parses weight of the edge, missing weight is replaced with `W::default()`
+pub(crate) fn parse_weight<W: Label>(s: Option<&str>, n: usize) -> Result<W, ParseGraphError> {
+    s.map_or_else(|| Ok(W::default()), |s| parse_label(s, n))
+}
+
+/// Returns number of vertices (the largest label) and edges between labels of the vertices
+#[allow(clippy::type_complexity)]
+pub(crate) fn numbered_edges<W: Label>(
+    graph: &Graph<usize, W>,
+) -> Result<(usize, Vec<(usize, usize, &W)>), fmt::Error> {
+    let vertices = graph.vertices_data.values().copied().max().unwrap_or(0);
+    let mut edges = Vec::with_capacity(graph.edges_count());
+    for (_, neighbours) in graph.vertices.iter() {
+        for ([from, to], weight) in neighbours {
+            let from = *graph.get_vertex(*from).map_err(|_| fmt::Error)?;
+            let to = *graph.get_vertex(*to).map_err(|_| fmt::Error)?;
+            edges.push((from, to, weight));
+        }
+    }
+    Ok((vertices, edges))
+}
+
+/// Maximum flow problem in DIMACS format, see [`Graph::from_dimacs_max_flow`]
+///
+/// Implements [`Display`] which formats it back to DIMACS format
+///
+/// ```
+/// use simple_graph::{DimacsMaxFlow, Graph};
+///
+/// let s = concat!(
+///     "p max 3 2\n",
+///     "n 1 s\n",
+///     "n 3 t\n",
+///     "a 1 2 5\n",
+///     "a 2 3 4\n",
+/// );
+/// let problem = Graph::<usize, u32>::from_dimacs_max_flow(s).unwrap();
+/// assert_eq!((problem.source, problem.sink), (1, 3));
+/// assert_eq!(problem.to_string(), s);
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DimacsMaxFlow<W: Label> {
+    /// network where labels of the edges are capacities
+    pub graph: Graph<usize, W>,
+    /// number of the source vertex
+    pub source: usize,
+    /// number of the sink vertex
+    pub sink: usize,
+}
+
+impl<W: Label> Display for DimacsMaxFlow<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (vertices, edges) = numbered_edges(&self.graph)?;
+        writeln!(f, "p max {vertices} {}", edges.len())?;
+        writeln!(f, "n {} s", self.source)?;
+        writeln!(f, "n {} t", self.sink)?;
+        for (from, to, capacity) in edges {
+            writeln!(f, "a {from} {to} {capacity}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats [`Graph<usize, W>`] in DIMACS shortest path format (`.gr`)
+///
+/// Labels of the vertices are used as their numbers, so they must start from 1
+///
+/// ```
+/// use simple_graph::{Dimacs, Graph};
+///
+/// let s = concat!(
+///     "p sp 3 2\n",
+///     "a 1 2 180\n",
+///     "a 2 3 225\n",
+/// );
+/// let graph = Graph::<usize, u32>::from_dimacs(s).unwrap();
+/// assert_eq!(Dimacs(&graph).to_string(), s);
+/// ```
+pub struct Dimacs<'a, W: Label>(pub &'a Graph<usize, W>);
+
+impl<W: Label> Display for Dimacs<'_, W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (vertices, edges) = numbered_edges(self.0)?;
+        writeln!(f, "p sp {vertices} {}", edges.len())?;
+        for (from, to, weight) in edges {
+            writeln!(f, "a {from} {to} {weight}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Problem line, designations of the source and the sink and arcs of DIMACS file
+struct DimacsProblem<W: Label> {
+    graph: NumberedGraph<W>,
+    source: Option<usize>,
+    sink: Option<usize>,
+}
+
+fn parse_dimacs<W: Label>(s: &str, problem: &str) -> Result<DimacsProblem<W>, ParseGraphError> {
+    let mut result: Option<DimacsProblem<W>> = None;
+    for (n, line) in (1_usize..).zip(s.lines()) {
+        let mut it = line.split_whitespace();
+        match it.next() {
+            None | Some("c") => {}
+            Some("p") => {
+                if result.is_some() {
+                    return Err(ParseGraphError::Syntax("duplicate problem line", n));
+                }
+                if it.next() != Some(problem) {
+                    return Err(ParseGraphError::Syntax("unexpected problem type", n));
+                }
+                let vertices = parse_index(it.next().unwrap_or(""), n)?;
+                result = Some(DimacsProblem {
+                    graph: NumberedGraph::new(vertices, n)?,
+                    source: None,
+                    sink: None,
+                });
+            }
+            Some(kind @ ("n" | "a")) => {
+                let result = result
+                    .as_mut()
+                    .ok_or(ParseGraphError::Syntax("expected problem line", n))?;
+                let from = parse_index(it.next().unwrap_or(""), n)?;
+                let value = it.next();
+                match (kind, value) {
+                    ("n", Some("s")) => result.source = Some(from),
+                    ("n", Some("t")) => result.sink = Some(from),
+                    ("n", _) => return Err(ParseGraphError::Syntax("expected `s` or `t`", n)),
+                    _ => {
+                        let to = parse_index(value.unwrap_or(""), n)?;
+                        let weight = parse_weight(it.next(), n)?;
+                        result.graph.add_edge(from, to, weight, n)?;
+                    }
+                }
+            }
+            Some(_) => return Err(ParseGraphError::Syntax("unknown line type", n)),
+        }
+    }
+    result.ok_or(ParseGraphError::Syntax("expected problem line", 1))
+}
+
+impl<W: Label> Graph<usize, W> {
+    /// Parses [`Graph<usize, W>`] from [`&str`] in DIMACS shortest path format (`.gr`)
+    ///
+    /// Vertices are labeled by their numbers `1..=n`, labels of the edges are arc weights.
+    /// Returns [`ParseGraphError::Syntax`] if `n` is larger than 2<sup>24</sup>
+    ///
+    /// ```
+    /// use simple_graph::{Graph, ParseGraphError};
+    ///
+    /// let s = concat!(
+    ///     "c 9th DIMACS Implementation Challenge\n",
+    ///     "p sp 3 2\n",
+    ///     "a 1 2 180\n",
+    ///     "a 2 3 225\n",
+    /// );
+    /// let graph = Graph::<usize, u32>::from_dimacs(s).unwrap();
+    /// assert_eq!(graph.vertices_count(), 3);
+    /// assert_eq!(graph.get_edge_value(graph.get_vertex_id(&2), graph.get_vertex_id(&3)), Ok(&225));
+    ///
+    /// let huge = Graph::<usize, u32>::from_dimacs("p sp 18446744073709551615 0\n");
+    /// assert_eq!(huge, Err(ParseGraphError::Syntax("too many vertices", 1)));
+    /// ```
+    pub fn from_dimacs(s: &str) -> Result<Self, ParseGraphError> {
+        Ok(parse_dimacs(s, "sp")?.graph.finish())
+    }
+
+    /// Parses maximum flow problem from [`&str`] in DIMACS format,
+    /// labels of the edges are arc capacities
+    pub fn from_dimacs_max_flow(s: &str) -> Result<DimacsMaxFlow<W>, ParseGraphError> {
+        let problem = parse_dimacs(s, "max")?;
+        let source = problem
+            .source
+            .ok_or(ParseGraphError::Syntax("source is not defined", 1))?;
+        let sink = problem
+            .sink
+            .ok_or(ParseGraphError::Syntax("sink is not defined", 1))?;
+        Ok(DimacsMaxFlow {
+            graph: problem.graph.finish(),
+            source,
+            sink,
+        })
+    }
+}
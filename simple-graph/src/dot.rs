@@ -0,0 +1,61 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::{Graph, Label, TgfLayout, TgfOrder};
+
+/// Formats [`Graph<V, E>`] in [DOT](https://graphviz.org/doc/info/lang.html) language of Graphviz
+///
+/// Vertices are numbered from 1 in insertion order, labels are written as `label` attributes
+///
+/// ```
+/// use simple_graph::{Dot, Graph};
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, String> =
+///     Graph::from_str(include_str!("../test_input/default.tgf")).unwrap();
+///
+/// let expected = concat!(
+///     "digraph {\n",
+///     "    1 [label=\"First node\"];\n",
+///     "    2 [label=\"Second node\"];\n",
+///     "    1 -> 2 [label=\"Edge between the two\"];\n",
+///     "}\n",
+/// );
+/// assert_eq!(Dot(&graph).to_string(), expected);
+/// ```
+pub struct Dot<'a, V: Label, E: Label>(pub &'a Graph<V, E>);
+
+/// Escapes special characters of the quoted DOT string
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl<V: Label, E: Label> Display for Dot<'_, V, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let layout = TgfLayout::new(self.0, None, TgfOrder::Insertion).map_err(|_| fmt::Error)?;
+
+        writeln!(f, "digraph {{")?;
+        for (index, vertex) in layout.vertices {
+            writeln!(
+                f,
+                "    {index} [label=\"{}\"];",
+                escape(&vertex.to_string())
+            )?;
+        }
+        for (from, to, edge) in layout.edges {
+            match edge.to_string() {
+                edge if edge.is_empty() => writeln!(f, "    {from} -> {to};")?,
+                edge => writeln!(f, "    {from} -> {to} [label=\"{}\"];", escape(&edge))?,
+            }
+        }
+        writeln!(f, "}}")
+    }
+}
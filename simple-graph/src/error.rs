@@ -1,8 +1,10 @@
+use std::io;
+
 /// Custom Result type with two generic parameters for user convenience
 pub type Result<T, E = GraphOperationError> = std::result::Result<T, E>;
 
 /// Describes possible errors that might happen when user interacts with graph
-#[derive(thiserror::Error, Debug, Eq, PartialEq)]
+#[derive(thiserror::Error, Debug, Clone, Eq, PartialEq)]
 pub enum GraphOperationError {
     /// when user trying to create vertex with the same data
     #[error("this vertex_id already exists in the graph")]
@@ -13,10 +15,39 @@ pub enum GraphOperationError {
     /// when user trying to find edge by two vertices and it's failed
     #[error("unable to find edge in graph between two vertices")]
     EdgeDoesNotExist,
+    /// when adjacency matrix isn't square or doesn't match the number of vertex labels
+    #[error("adjacency matrix size doesn't match the number of vertices")]
+    MatrixSizeMismatch,
+    /// when length of the edge in the shortest path search is negative or isn't a number
+    #[error("edge length must be a non-negative number")]
+    InvalidEdgeLength,
+}
+
+/// Describes possible errors of transactions and history of changes,
+/// see [`crate::TransactionalGraph`]
+#[derive(thiserror::Error, Debug, Clone, Eq, PartialEq)]
+pub enum TransactionError {
+    /// when transaction is started again or history is changed before it's finished
+    #[error("transaction is already started")]
+    TransactionAlreadyStarted,
+    /// when transaction is committed or rolled back but it isn't started
+    #[error("there is no started transaction")]
+    NoTransaction,
+    /// when undo history is empty
+    #[error("there are no changes to undo")]
+    NothingToUndo,
+    /// when redo history is empty
+    #[error("there are no undone changes to redo")]
+    NothingToRedo,
+
+    /// internal error with graphs API
+    #[error("some graph operation failed: {0}")]
+    GraphError(GraphOperationError),
 }
 
 /// Describes possible errors that might happen during parsing the Trivial Graph Format
-#[derive(thiserror::Error, Debug, Eq, PartialEq)]
+/// and other text formats such as GML or Pajek NET
+#[derive(thiserror::Error, Debug, Clone, Eq, PartialEq)]
 pub enum ParseGraphError {
     /// `(line: usize)`
     #[error("incorrect vertex definition at line {0}")]
@@ -39,7 +70,70 @@ pub enum ParseGraphError {
     #[error("failed to parse label data of the vertex or edge at line {0}")]
     ParseLabel(usize),
 
+    /// `(message: &'static str, line: usize)`
+    #[error("syntax error: {0} at line {1}")]
+    Syntax(&'static str, usize),
+    /// `(kind: io::ErrorKind, line: usize)`
+    #[error("failed to read input: {0} at line {1}")]
+    Io(io::ErrorKind, usize),
+
     /// internal error with graphs API
     #[error("some graph operation failed: {0} at line {1}")]
     GraphError(GraphOperationError, usize),
 }
+
+/// Describes possible errors that might happen during decoding the binary format,
+/// see [`crate::Graph::from_binary`]
+#[derive(thiserror::Error, Debug, Clone, Eq, PartialEq)]
+pub enum DecodeGraphError {
+    /// when input doesn't start with magic bytes
+    #[error("input is not a binary graph, magic bytes are missing")]
+    InvalidMagic,
+    /// `(version: u8)`
+    #[error("unsupported version {0} of the binary format")]
+    UnsupportedVersion(u8),
+    /// `(offset: usize)`
+    #[error("unexpected end of input at byte {0}")]
+    UnexpectedEnd(usize),
+    /// `(expected: u32, found: u32)`
+    #[error("checksum mismatch, expected {0:#010x} but found {1:#010x}")]
+    ChecksumMismatch(u32, u32),
+    /// `(offset: usize)`
+    #[error("failed to decode label of the vertex or edge at byte {0}")]
+    InvalidLabel(usize),
+    /// `(offset: usize)`
+    #[error("invalid index of the vertex at byte {0}")]
+    InvalidVertexIndex(usize),
+    /// `(offset: usize)`
+    #[error("unexpected trailing data at byte {0}")]
+    TrailingBytes(usize),
+
+    /// internal error with graphs API
+    #[error("some graph operation failed: {0}")]
+    GraphError(GraphOperationError),
+}
+
+impl ParseGraphError {
+    /// Returns number of the line where error happened, starting from 1
+    ///
+    /// ```
+    /// use simple_graph::{Graph, ParseGraphError};
+    /// use std::str::FromStr;
+    ///
+    /// let error = Graph::<String, u32>::from_str("1 Moscow\n#\n1 2 180\n").unwrap_err();
+    /// assert_eq!(error.line(), 3);
+    /// ```
+    pub fn line(&self) -> usize {
+        match *self {
+            ParseGraphError::VertexDefinition(line)
+            | ParseGraphError::EdgeDefinition(line)
+            | ParseGraphError::VertexAlreadyDefined(_, line)
+            | ParseGraphError::VerticesNotDefined(_, _, line)
+            | ParseGraphError::ParseInt(line)
+            | ParseGraphError::ParseLabel(line)
+            | ParseGraphError::Syntax(_, line)
+            | ParseGraphError::Io(_, line)
+            | ParseGraphError::GraphError(_, line) => line,
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::ops::Add;
+
+use super::{
+    Csr, EdgeWeights, Graph, GraphBase, GraphOperationError, Label, Neighbors, Result, VertexCount,
+    VertexId, Vertices, shortest_path, traits,
+};
+
+/// Immutable graph in compressed sparse row format, vertices are numbered by dense
+/// indices in insertion order, see [`Graph::freeze`]
+///
+/// Edges are stored in [`Csr`] with their labels as values, so neighbours are read without hashing
+///
+/// ```
+/// use simple_graph::Graph;
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+/// let frozen = graph.freeze();
+///
+/// let moscow = frozen.index(graph.get_vertex_id(&"Moscow".into())).unwrap();
+/// let neighbours = frozen.neighbours(moscow).unwrap().collect::<Vec<_>>();
+/// assert_eq!(neighbours, [(1, &180), (2, &250)]);
+///
+/// assert_eq!(frozen.get_vertex(1), Ok(&"Vladimir".to_string()));
+/// assert_eq!(frozen.vertex_id(1), Ok(graph.get_vertex_id(&"Vladimir".into())));
+/// assert_eq!(frozen.csr().columns, [1, 2, 3, 4]);
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct FrozenGraph<V: Label, E: Label> {
+    labels: Vec<V>,
+    csr: Csr<E>,
+}
+
+impl<V: Label, E: Label> Graph<V, E> {
+    /// Copies the graph into [`FrozenGraph`] for fast read-only access, vertices and edges
+    /// keep insertion order
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let frozen = graph.freeze();
+    ///
+    /// assert_eq!(frozen.vertices_count(), 5);
+    /// assert_eq!(frozen.edges_count(), 4);
+    /// ```
+    pub fn freeze(&self) -> FrozenGraph<V, E> {
+        let csr = self.to_csr(E::clone);
+        let labels = csr
+            .vertices
+            .iter()
+            .map(|vertex_id| self.vertices_data[vertex_id].clone())
+            .collect();
+        FrozenGraph { labels, csr }
+    }
+}
+
+impl<V: Label, E: Label> FrozenGraph<V, E> {
+    /// Returns count of vertices in the graph
+    pub fn vertices_count(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Returns count of edges in the graph
+    pub fn edges_count(&self) -> usize {
+        self.csr.columns.len()
+    }
+
+    /// Returns edges of the graph in [`Csr`] format with labels as values
+    pub fn csr(&self) -> &Csr<E> {
+        &self.csr
+    }
+
+    /// Returns index of the vertex, returns [`GraphOperationError::VertexDoesNotExist`]
+    /// if the vertex isn't in the graph
+    pub fn index(&self, vertex_id: VertexId) -> Result<usize> {
+        self.csr
+            .indices
+            .get(&vertex_id)
+            .copied()
+            .ok_or(GraphOperationError::VertexDoesNotExist)
+    }
+
+    /// Returns id of the vertex in the original graph, returns
+    /// [`GraphOperationError::VertexDoesNotExist`] if the index is out of range
+    pub fn vertex_id(&self, index: usize) -> Result<VertexId> {
+        self.csr
+            .vertices
+            .get(index)
+            .copied()
+            .ok_or(GraphOperationError::VertexDoesNotExist)
+    }
+
+    /// Returns label of the vertex, returns [`GraphOperationError::VertexDoesNotExist`]
+    /// if the index is out of range
+    pub fn get_vertex(&self, index: usize) -> Result<&V> {
+        self.labels
+            .get(index)
+            .ok_or(GraphOperationError::VertexDoesNotExist)
+    }
+
+    /// Returns indices of the adjacent vertices with labels of the edges to them,
+    /// returns [`GraphOperationError::VertexDoesNotExist`] if the index is out of range
+    pub fn neighbours(&self, index: usize) -> Result<impl Iterator<Item = (usize, &E)>> {
+        self.get_vertex(index)?;
+        Ok(self.csr.row(index))
+    }
+
+    /// Same as [`Graph::get_vertex_info`], but the vertex is identified by its index
+    pub fn get_vertex_info(&self, index: usize) -> Result<(&V, Vec<(&V, &E)>)> {
+        let vertex = self.get_vertex(index)?;
+        let adjacent_vertices = self
+            .neighbours(index)?
+            .map(|(to, edge)| (&self.labels[to], edge))
+            .collect();
+        Ok((vertex, adjacent_vertices))
+    }
+
+    /// Same as [`Graph::bfs`], but the source is identified by its index
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let frozen = graph.freeze();
+    ///
+    /// let mut visited = Vec::new();
+    /// frozen.bfs(0, |vertex, _| visited.push(vertex.clone())).unwrap();
+    /// assert_eq!(visited, ["Moscow", "Vladimir", "Yaroslavl", "Novgorod", "Vologda"]);
+    /// ```
+    pub fn bfs<F: FnMut(&V, Vec<(&V, &E)>)>(&self, source: usize, access: F) -> Result<()> {
+        traits::bfs(self, source, access)
+    }
+
+    /// Same as [`Graph::dfs`], but the source is identified by its index
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let frozen = graph.freeze();
+    ///
+    /// let mut visited = Vec::new();
+    /// frozen.dfs(0, |vertex, _| visited.push(vertex.clone())).unwrap();
+    /// assert_eq!(visited, ["Moscow", "Yaroslavl", "Vologda", "Vladimir", "Novgorod"]);
+    /// ```
+    pub fn dfs<F: FnMut(&V, Vec<(&V, &E)>)>(&self, source: usize, access: F) -> Result<()> {
+        traits::dfs(self, source, access)
+    }
+
+    /// Same as [`Graph::shortest_path`], but vertices are identified by their indices
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let frozen = graph.freeze();
+    /// let index = |label: &str| frozen.index(graph.get_vertex_id(&label.into())).unwrap();
+    ///
+    /// let (distance, path) = frozen
+    ///     .shortest_path(index("Moscow"), index("Vologda"), |&km| km)
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert_eq!(distance, 425);
+    /// assert_eq!(path, [index("Moscow"), index("Yaroslavl"), index("Vologda")]);
+    ///
+    /// assert_eq!(frozen.shortest_path(index("Vologda"), index("Moscow"), |&km| km), Ok(None));
+    /// ```
+    pub fn shortest_path<W, F>(
+        &self,
+        from: usize,
+        to: usize,
+        weight: F,
+    ) -> Result<Option<(W, Vec<usize>)>>
+    where
+        W: Copy + Default + PartialOrd + Add<Output = W>,
+        F: Fn(&E) -> W,
+    {
+        shortest_path(self, from, to, weight)
+    }
+}
+
+impl<V: Label, E: Label> GraphBase for FrozenGraph<V, E> {
+    type VertexId = usize;
+    type Vertex = V;
+    type Edge = E;
+
+    fn vertex(&self, index: usize) -> Result<&V> {
+        self.get_vertex(index)
+    }
+}
+
+impl<V: Label, E: Label> VertexCount for FrozenGraph<V, E> {
+    fn vertices_count(&self) -> usize {
+        FrozenGraph::vertices_count(self)
+    }
+}
+
+impl<V: Label, E: Label> Vertices for FrozenGraph<V, E> {
+    fn vertex_ids(&self) -> impl Iterator<Item = usize> {
+        0..self.vertices_count()
+    }
+}
+
+impl<V: Label, E: Label> Neighbors for FrozenGraph<V, E> {
+    fn neighbors(&self, index: usize) -> Result<impl Iterator<Item = (usize, &E)>> {
+        self.neighbours(index)
+    }
+}
+
+impl<V: Label, E: Label> EdgeWeights for FrozenGraph<V, E> {
+    fn edge_weight(&self, from: usize, to: usize) -> Result<&E> {
+        self.neighbours(from)
+            .map_err(|_| GraphOperationError::EdgeDoesNotExist)?
+            .find(|(index, _)| *index == to)
+            .map(|(_, edge)| edge)
+            .ok_or(GraphOperationError::EdgeDoesNotExist)
+    }
+}
@@ -0,0 +1,341 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+use std::hash::Hash;
+
+use super::{Graph, Label, VertexId};
+
+/// Formats [`Graph<V, E>`] in [GEXF](https://gexf.net) format which is used by Gephi
+///
+/// Labels of the edges are exported as `label` attribute, parallel edges are exported separately
+///
+/// ```
+/// use simple_graph::{Gexf, Graph};
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, String> =
+///     Graph::from_str(include_str!("../test_input/default.tgf")).unwrap();
+///
+/// let expected = concat!(
+///     "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+///     "<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n",
+///     "  <graph mode=\"static\" defaultedgetype=\"directed\">\n",
+///     "    <attributes class=\"edge\" mode=\"static\">\n",
+///     "      <attribute id=\"label\" title=\"label\" type=\"string\"/>\n",
+///     "    </attributes>\n",
+///     "    <nodes>\n",
+///     "      <node id=\"1\" label=\"First node\"/>\n",
+///     "      <node id=\"2\" label=\"Second node\"/>\n",
+///     "    </nodes>\n",
+///     "    <edges>\n",
+///     "      <edge id=\"1\" source=\"1\" target=\"2\">\n",
+///     "        <attvalues>\n",
+///     "          <attvalue for=\"label\" value=\"Edge between the two\"/>\n",
+///     "        </attvalues>\n",
+///     "      </edge>\n",
+///     "    </edges>\n",
+///     "  </graph>\n",
+///     "</gexf>\n",
+/// );
+/// assert_eq!(Gexf(&graph).to_string(), expected);
+///
+/// // parallel edges are written separately
+/// let mut graph = Graph::<String, String>::new();
+/// let moscow = graph.add_vertex("Moscow".into()).unwrap();
+/// let vladimir = graph.add_vertex("Vladimir".into()).unwrap();
+/// graph.add_edge(moscow, vladimir, "road".into()).unwrap();
+/// graph.add_edge(moscow, vladimir, "railway".into()).unwrap();
+///
+/// let gexf = Gexf(&graph).to_string();
+/// assert_eq!(gexf.matches("<edge ").count(), 2);
+/// assert!(gexf.contains("<attvalue for=\"label\" value=\"road\"/>"));
+/// assert!(gexf.contains("<attvalue for=\"label\" value=\"railway\"/>"));
+/// ```
+pub struct Gexf<'a, V: Label, E: Label>(pub &'a Graph<V, E>);
+
+impl<V: Label, E: Label> Display for Gexf<'_, V, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let timeline = GexfTimeline::new().snapshot("", self.0);
+        write_gexf(f, &timeline, false)
+    }
+}
+
+/// Formats sequence of [`Graph<V, E>`] snapshots as dynamic graph in GEXF format,
+/// so evolution of the graph can be replayed in Gephi
+///
+/// Every vertex and edge gets `start`/`end` spells for the periods when it exists.
+/// Snapshot is valid until time of the next snapshot, elements of the last snapshot have no `end`.
+/// Changes of the edge labels are exported as dynamic `label` attribute,
+/// parallel edges are matched between snapshots by their order among edges between the same vertices
+///
+/// ```
+/// use simple_graph::{GexfTimeline, Graph};
+///
+/// let mut graph = Graph::<String, u32>::new();
+/// let moscow = graph.add_vertex("Moscow".into()).unwrap();
+/// let vladimir = graph.add_vertex("Vladimir".into()).unwrap();
+/// graph.add_edge(moscow, vladimir, 180).unwrap();
+/// let first = graph.clone();
+///
+/// graph.remove_vertex(vladimir).unwrap();
+/// let second = graph.clone();
+///
+/// let gexf = GexfTimeline::new()
+///     .snapshot(2001, &first)
+///     .snapshot(2002, &second)
+///     .time_format("integer")
+///     .to_string();
+///
+/// assert!(gexf.contains("<graph mode=\"dynamic\" defaultedgetype=\"directed\" timeformat=\"integer\">"));
+/// assert!(gexf.contains("<node id=\"1\" label=\"Moscow\">\n        <spells>\n          <spell start=\"2001\"/>"));
+/// assert!(gexf.contains("<node id=\"2\" label=\"Vladimir\">\n        <spells>\n          <spell start=\"2001\" end=\"2002\"/>"));
+/// ```
+pub struct GexfTimeline<'a, T, V: Label, E: Label> {
+    snapshots: Vec<(T, &'a Graph<V, E>)>,
+    time_format: &'a str,
+}
+
+impl<'a, T: Display, V: Label, E: Label> GexfTimeline<'a, T, V, E> {
+    /// Creates empty timeline with `double` time format
+    pub fn new() -> Self {
+        Self {
+            snapshots: Vec::new(),
+            time_format: "double",
+        }
+    }
+
+    /// Adds state of the graph at specified time, snapshots must be added in chronological order
+    pub fn snapshot(mut self, time: T, graph: &'a Graph<V, E>) -> Self {
+        self.snapshots.push((time, graph));
+        self
+    }
+
+    /// Sets GEXF `timeformat`, for example `integer`, `double`, `date` or `dateTime`
+    pub fn time_format(mut self, time_format: &'a str) -> Self {
+        self.time_format = time_format;
+        self
+    }
+}
+
+impl<T: Display, V: Label, E: Label> Default for GexfTimeline<'_, T, V, E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Display, V: Label, E: Label> Display for GexfTimeline<'_, T, V, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_gexf(f, self, true)
+    }
+}
+
+/// Edges of the graph keyed by `(from, to, k)` where `k` is position of the edge
+/// among parallel edges between the same vertices
+fn parallel_edges<V: Label, E: Label>(
+    graph: &Graph<V, E>,
+) -> impl Iterator<Item = ((VertexId, VertexId, usize), &E)> {
+    let mut counts = HashMap::new();
+    graph
+        .vertices
+        .values()
+        .flatten()
+        .map(move |([from, to], edge)| {
+            let k = counts.entry((*from, *to)).or_insert(0);
+            *k += 1;
+            ((*from, *to, *k - 1), edge)
+        })
+}
+
+/// Period `[start, end)` in indices of snapshots, `end` is `None` if element exists in the last snapshot
+type Spell = (usize, Option<usize>);
+
+/// Collects periods of existence for every key in the order of the first appearance
+fn spells<K: Copy + Eq + Hash, I: Iterator<Item = K>>(
+    snapshots: impl Iterator<Item = I>,
+) -> Vec<(K, Vec<Spell>)> {
+    let mut order = Vec::new();
+    let mut spells = HashMap::<K, Vec<Spell>>::new();
+    let mut open = HashMap::<K, usize>::new();
+
+    for (n, keys) in snapshots.enumerate() {
+        let mut present = HashSet::new();
+        for key in keys {
+            present.insert(key);
+            open.entry(key).or_insert(n);
+            spells.entry(key).or_insert_with(|| {
+                order.push(key);
+                Vec::new()
+            });
+        }
+        open.retain(|key, start| {
+            let keep = present.contains(key);
+            if !keep {
+                spells.entry(*key).or_default().push((*start, Some(n)));
+            }
+            keep
+        });
+    }
+    for (key, start) in open {
+        spells.entry(key).or_default().push((start, None));
+    }
+
+    order
+        .into_iter()
+        .map(|key| {
+            let mut list = spells.remove(&key).unwrap_or_default();
+            list.sort();
+            (key, list)
+        })
+        .collect()
+}
+
+fn write_gexf<T: Display, V: Label, E: Label>(
+    f: &mut Formatter<'_>,
+    timeline: &GexfTimeline<'_, T, V, E>,
+    dynamic: bool,
+) -> fmt::Result {
+    let snapshots = &timeline.snapshots;
+    let time = |n: usize| &snapshots[n].0;
+
+    let vertices = spells(
+        snapshots
+            .iter()
+            .map(|(_, graph)| graph.vertices.keys().copied()),
+    );
+    let labels = snapshots
+        .iter()
+        .map(|(_, graph)| parallel_edges(graph).collect::<HashMap<_, _>>())
+        .collect::<Vec<_>>();
+    let edges = spells(
+        snapshots
+            .iter()
+            .map(|(_, graph)| parallel_edges(graph).map(|(key, _)| key)),
+    );
+
+    let ids = vertices
+        .iter()
+        .enumerate()
+        .map(|(n, (vertex_id, _))| (*vertex_id, n + 1))
+        .collect::<HashMap<VertexId, usize>>();
+
+    let mode = if dynamic { "dynamic" } else { "static" };
+    writeln!(f, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(f, "<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">")?;
+    match dynamic {
+        true => writeln!(
+            f,
+            "  <graph mode=\"{mode}\" defaultedgetype=\"directed\" timeformat=\"{}\">",
+            escape_xml(timeline.time_format)
+        )?,
+        false => writeln!(f, "  <graph mode=\"{mode}\" defaultedgetype=\"directed\">")?,
+    }
+    writeln!(f, "    <attributes class=\"edge\" mode=\"{mode}\">")?;
+    writeln!(
+        f,
+        "      <attribute id=\"label\" title=\"label\" type=\"string\"/>"
+    )?;
+    writeln!(f, "    </attributes>")?;
+
+    let write_spells = |f: &mut Formatter<'_>, spells: &[Spell]| -> fmt::Result {
+        writeln!(f, "        <spells>")?;
+        for &(start, end) in spells {
+            write!(f, "          <spell start=\"{}\"", time(start))?;
+            if let Some(end) = end {
+                write!(f, " end=\"{}\"", time(end))?;
+            }
+            writeln!(f, "/>")?;
+        }
+        writeln!(f, "        </spells>")
+    };
+
+    writeln!(f, "    <nodes>")?;
+    for (vertex_id, spells) in &vertices {
+        let n = spells.first().map_or(0, |&(start, _)| start);
+        let label = snapshots[n]
+            .1
+            .get_vertex(*vertex_id)
+            .map_err(|_| fmt::Error)?;
+        let label = escape_xml(&label.to_string());
+        match dynamic {
+            true => {
+                writeln!(
+                    f,
+                    "      <node id=\"{}\" label=\"{label}\">",
+                    ids[vertex_id]
+                )?;
+                write_spells(f, spells)?;
+                writeln!(f, "      </node>")?;
+            }
+            false => writeln!(
+                f,
+                "      <node id=\"{}\" label=\"{label}\"/>",
+                ids[vertex_id]
+            )?,
+        }
+    }
+    writeln!(f, "    </nodes>")?;
+
+    writeln!(f, "    <edges>")?;
+    for (n, (key @ (from, to, _), spells)) in (1_usize..).zip(edges.iter()) {
+        let (source, target) = ids.get(from).zip(ids.get(to)).ok_or(fmt::Error)?;
+        writeln!(
+            f,
+            "      <edge id=\"{n}\" source=\"{source}\" target=\"{target}\">"
+        )?;
+
+        // runs of the same label while the edge exists, `None` marks absence of the edge
+        let mut runs: Vec<(String, usize, Option<usize>)> = Vec::new();
+        for (i, labels) in labels.iter().enumerate() {
+            let label = labels.get(key).map(|label| label.to_string());
+            match (runs.last_mut(), label) {
+                (Some((value, _, end @ None)), Some(label)) if *value != label => {
+                    *end = Some(i);
+                    runs.push((label, i, None));
+                }
+                (Some((_, _, None)), Some(_)) => {}
+                (Some((_, _, end @ None)), None) => *end = Some(i),
+                (_, Some(label)) => runs.push((label, i, None)),
+                (_, None) => {}
+            }
+        }
+
+        writeln!(f, "        <attvalues>")?;
+        for (value, start, end) in &runs {
+            write!(
+                f,
+                "          <attvalue for=\"label\" value=\"{}\"",
+                escape_xml(value)
+            )?;
+            if dynamic {
+                write!(f, " start=\"{}\"", time(*start))?;
+                if let Some(end) = end {
+                    write!(f, " end=\"{}\"", time(*end))?;
+                }
+            }
+            writeln!(f, "/>")?;
+        }
+        writeln!(f, "        </attvalues>")?;
+        if dynamic {
+            write_spells(f, spells)?;
+        }
+        writeln!(f, "      </edge>")?;
+    }
+    writeln!(f, "    </edges>")?;
+    writeln!(f, "  </graph>")?;
+    writeln!(f, "</gexf>")
+}
+
+/// Escapes special characters of XML attribute values and text
+pub(crate) fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,227 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::{
+    Graph, Label, ParseGraphError, TgfEvent, TgfGraphBuilder, TgfLayout, TgfOptions, TgfOrder,
+    parse_label,
+};
+
+/// Formats [`Graph<V, E>`] in [Graph Modelling Language](https://en.wikipedia.org/wiki/Graph_Modelling_Language)
+///
+/// Vertices are numbered from 1 in insertion order, labels of the vertices and edges
+/// are written as `label` strings
+///
+/// ```
+/// use simple_graph::{Gml, Graph};
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, String> =
+///     Graph::from_str(include_str!("../test_input/default.tgf")).unwrap();
+///
+/// let expected = concat!(
+///     "graph [\n",
+///     "  directed 1\n",
+///     "  node [\n",
+///     "    id 1\n",
+///     "    label \"First node\"\n",
+///     "  ]\n",
+///     "  node [\n",
+///     "    id 2\n",
+///     "    label \"Second node\"\n",
+///     "  ]\n",
+///     "  edge [\n",
+///     "    source 1\n",
+///     "    target 2\n",
+///     "    label \"Edge between the two\"\n",
+///     "  ]\n",
+///     "]\n",
+/// );
+/// assert_eq!(Gml(&graph).to_string(), expected);
+/// assert_eq!(Graph::from_gml(expected).unwrap(), graph);
+/// ```
+pub struct Gml<'a, V: Label, E: Label>(pub &'a Graph<V, E>);
+
+impl<V: Label, E: Label> Display for Gml<'_, V, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let layout = TgfLayout::new(self.0, None, TgfOrder::Insertion).map_err(|_| fmt::Error)?;
+
+        writeln!(f, "graph [")?;
+        writeln!(f, "  directed 1")?;
+        for (index, vertex) in layout.vertices {
+            writeln!(f, "  node [")?;
+            writeln!(f, "    id {index}")?;
+            writeln!(f, "    label \"{}\"", escape(&vertex.to_string()))?;
+            writeln!(f, "  ]")?;
+        }
+        for (from, to, edge) in layout.edges {
+            writeln!(f, "  edge [")?;
+            writeln!(f, "    source {from}")?;
+            writeln!(f, "    target {to}")?;
+            writeln!(f, "    label \"{}\"", escape(&edge.to_string()))?;
+            writeln!(f, "  ]")?;
+        }
+        writeln!(f, "]")
+    }
+}
+
+impl<V: Label, E: Label> Graph<V, E> {
+    /// Parses [`Graph<V, E>`] from [`&str`] in Graph Modelling Language
+    ///
+    /// Label of the vertex is taken from `label` key or from `id` if it's missing.
+    /// Label of the edge is taken from `label`, `weight` or `value` keys.
+    /// Edges of the undirected graph (`directed 0`, which is default) are added in both directions
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    ///
+    /// let s = r#"
+    /// ## distances between cities
+    /// graph [
+    ///   directed 1
+    ///   node [ id 1 label "Moscow" ]
+    ///   node [ id 2 label "Vladimir" ]
+    ///   edge [ source 1 target 2 weight 180 ]
+    /// ]
+    /// "#;
+    /// let graph = Graph::<String, u32>::from_gml(s).unwrap();
+    ///
+    /// let moscow = graph.get_vertex_id(&"Moscow".into());
+    /// let vladimir = graph.get_vertex_id(&"Vladimir".into());
+    /// assert_eq!(graph.get_edge_value(moscow, vladimir), Ok(&180));
+    /// ```
+    pub fn from_gml(s: &str) -> Result<Self, ParseGraphError> {
+        let tokens = tokenize(s)?;
+        let mut it = tokens.into_iter();
+
+        let mut graph = None;
+        while let Some((token, n)) = it.next() {
+            match (token, parse_value(&mut it, n)?) {
+                (Token::Key("graph"), Value::List(list)) if graph.is_none() => graph = Some(list),
+                (Token::Key(_), _) => {}
+                _ => return Err(ParseGraphError::Syntax("expected key", n)),
+            }
+        }
+        let graph = graph.ok_or(ParseGraphError::Syntax("`graph` is not defined", 1))?;
+
+        let directed = graph.iter().any(|(key, value, _)| {
+            *key == "directed" && matches!(value, Value::Scalar(value) if value.trim() != "0")
+        });
+
+        let mut builder = TgfGraphBuilder::new(&TgfOptions::default());
+        for (key, value, n) in &graph {
+            if let ("node", Value::List(node)) = (*key, value) {
+                let index = find_index(node, "id", *n)?;
+                let id = index.to_string();
+                let label = find_scalar(node, &["label"]).unwrap_or(&id);
+                builder.push(TgfEvent::Vertex(index, parse_label(label, *n)?), *n)?;
+            }
+        }
+        for (key, value, n) in &graph {
+            if let ("edge", Value::List(edge)) = (*key, value) {
+                let from = find_index(edge, "source", *n)?;
+                let to = find_index(edge, "target", *n)?;
+                let label = find_scalar(edge, &["label", "weight", "value"]).unwrap_or("");
+                builder.push(TgfEvent::Edge(from, to, parse_label(label, *n)?), *n)?;
+                if !directed && from != to {
+                    builder.push(TgfEvent::Edge(to, from, parse_label(label, *n)?), *n)?;
+                }
+            }
+        }
+
+        Ok(builder.finish().0)
+    }
+}
+
+/// Escapes characters which can't be used inside of the GML string
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('"', "&quot;")
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&quot;", "\"").replace("&amp;", "&")
+}
+
+#[derive(Debug)]
+enum Token<'a> {
+    Key(&'a str),
+    Scalar(String),
+    Open,
+    Close,
+}
+
+#[derive(Debug)]
+enum Value<'a> {
+    Scalar(String),
+    List(Vec<(&'a str, Value<'a>, usize)>),
+}
+
+fn tokenize(s: &str) -> Result<Vec<(Token<'_>, usize)>, ParseGraphError> {
+    let mut tokens = Vec::new();
+    for (n, line) in (1_usize..).zip(s.lines()) {
+        let mut rest = line.trim_start();
+        while !rest.is_empty() && !rest.starts_with('#') {
+            let (token, tail) = if let Some(tail) = rest.strip_prefix('[') {
+                (Token::Open, tail)
+            } else if let Some(tail) = rest.strip_prefix(']') {
+                (Token::Close, tail)
+            } else if let Some(tail) = rest.strip_prefix('"') {
+                let end = tail
+                    .find('"')
+                    .ok_or(ParseGraphError::Syntax("unterminated string", n))?;
+                (Token::Scalar(unescape(&tail[..end])), &tail[end + 1..])
+            } else {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || c == '[' || c == ']')
+                    .unwrap_or(rest.len());
+                let (word, tail) = rest.split_at(end);
+                match word.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+                    true => (Token::Key(word), tail),
+                    false => (Token::Scalar(word.into()), tail),
+                }
+            };
+            tokens.push((token, n));
+            rest = tail.trim_start();
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_value<'a, I: Iterator<Item = (Token<'a>, usize)>>(
+    it: &mut I,
+    n: usize,
+) -> Result<Value<'a>, ParseGraphError> {
+    match it.next() {
+        Some((Token::Scalar(value), _)) => Ok(Value::Scalar(value)),
+        Some((Token::Key(value), _)) => Ok(Value::Scalar(value.into())),
+        Some((Token::Open, _)) => {
+            let mut list = Vec::new();
+            loop {
+                match it.next() {
+                    Some((Token::Close, _)) => return Ok(Value::List(list)),
+                    Some((Token::Key(key), n)) => list.push((key, parse_value(it, n)?, n)),
+                    Some((_, n)) => return Err(ParseGraphError::Syntax("expected key", n)),
+                    None => return Err(ParseGraphError::Syntax("expected `]`", n)),
+                }
+            }
+        }
+        Some((Token::Close, n)) => Err(ParseGraphError::Syntax("unexpected `]`", n)),
+        None => Err(ParseGraphError::Syntax("expected value", n)),
+    }
+}
+
+fn find_scalar<'a>(list: &'a [(&str, Value, usize)], keys: &[&str]) -> Option<&'a str> {
+    keys.iter().find_map(|key| {
+        list.iter().find_map(|(k, value, _)| match value {
+            Value::Scalar(value) if k == key => Some(value.as_str()),
+            _ => None,
+        })
+    })
+}
+
+fn find_index(
+    list: &[(&str, Value, usize)],
+    key: &str,
+    n: usize,
+) -> Result<usize, ParseGraphError> {
+    let value = find_scalar(list, &[key]).ok_or(ParseGraphError::Syntax("missing index", n))?;
+    value.parse().map_err(|_| ParseGraphError::ParseInt(n))
+}
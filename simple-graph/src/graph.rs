@@ -1,14 +1,25 @@
-use std::collections::{HashMap, HashSet, VecDeque, hash_map::DefaultHasher};
-use std::hash::Hasher;
+use std::collections::{HashMap, HashSet, hash_map::DefaultHasher};
+use std::hash::{Hash, Hasher};
 
 use linked_hash_map::LinkedHashMap;
 use linked_hash_set::LinkedHashSet;
 
-use super::{GraphOperationError, Label, Result};
+use super::{
+    Build, DuplicateVertexPolicy, EdgeWeights, GraphBase, GraphEvent, GraphOperationError, Label,
+    Neighbors, ObserverId, Observers, Result, VertexCount, Vertices, traits,
+};
 
 /// Represents hash of vertex and is used to access the HashMap
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-pub struct VertexId(u64);
+pub struct VertexId(pub(crate) u64);
+
+impl VertexId {
+    pub(crate) fn new<V: Hash>(vertex: &V) -> Self {
+        let mut hasher = DefaultHasher::new();
+        vertex.hash(&mut hasher);
+        VertexId(hasher.finish())
+    }
+}
 
 /// Directed graph data-structure with generic parameters
 ///
@@ -62,10 +73,11 @@ pub struct VertexId(u64);
 /// let mut graph_deserialized = Graph::from_str(&serialized).unwrap();
 /// assert_eq!(graph, graph_deserialized);
 /// ```
-#[derive(Debug, Default, Eq, PartialEq)]
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
 pub struct Graph<V: Label, E: Label> {
     pub(crate) vertices: LinkedHashMap<VertexId, LinkedHashSet<([VertexId; 2], E)>>,
     pub(crate) vertices_data: HashMap<VertexId, V>,
+    pub(crate) observers: Observers<V, E>,
 }
 
 impl<V: Label, E: Label> Graph<V, E> {
@@ -80,6 +92,22 @@ impl<V: Label, E: Label> Graph<V, E> {
         Self::default()
     }
 
+    /// Creates new graph with preallocated space for specified number of vertices
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    ///
+    /// let graph: Graph<usize, u32> = Graph::with_capacity(1000);
+    /// assert_eq!(graph.vertices_count(), 0);
+    /// ```
+    pub fn with_capacity(vertices: usize) -> Self {
+        Self {
+            vertices: LinkedHashMap::with_capacity(vertices),
+            vertices_data: HashMap::with_capacity(vertices),
+            observers: Observers::default(),
+        }
+    }
+
     /// Gets [`VertexId`] by it's value. It just creates hash of the `&V`, doesn't panics
     ///
     /// ```
@@ -97,9 +125,7 @@ impl<V: Label, E: Label> Graph<V, E> {
     /// assert_eq!(graph.get_edge_value(moscow, vladimir), Ok(&180));
     /// ```
     pub fn get_vertex_id(&self, vertex: &V) -> VertexId {
-        let mut hasher = DefaultHasher::new();
-        vertex.hash(&mut hasher);
-        VertexId(hasher.finish())
+        VertexId::new(vertex)
     }
 
     /// Trying to add vertex to the graph, returns [`GraphOperationError::VertexAlreadyExists`]
@@ -116,17 +142,70 @@ impl<V: Label, E: Label> Graph<V, E> {
     /// ```
     pub fn add_vertex(&mut self, vertex: V) -> Result<VertexId> {
         let vertex_id = self.get_vertex_id(&vertex);
-        if self
-            .vertices
-            .insert(vertex_id, LinkedHashSet::new())
-            .is_some()
-        {
+        if self.vertices.contains_key(&vertex_id) {
             return Err(GraphOperationError::VertexAlreadyExists);
         }
-        self.vertices_data.insert(vertex_id, vertex);
+        self.insert_vertex(vertex_id, vertex);
         Ok(vertex_id)
     }
 
+    /// Inserts vertex which is known to be absent and notifies observers
+    fn insert_vertex(&mut self, vertex_id: VertexId, vertex: V) {
+        self.vertices.insert(vertex_id, LinkedHashSet::new());
+        self.observers.notify(|| GraphEvent::VertexAdded {
+            vertex_id,
+            vertex: vertex.clone(),
+        });
+        self.vertices_data.insert(vertex_id, vertex);
+    }
+
+    /// Adds many vertices at once and returns their ids in the same order,
+    /// returns [`GraphOperationError::VertexAlreadyExists`] if any of them already exists
+    /// or is repeated in `vertices`, in this case none of the vertices are added
+    ///
+    /// Memory for all vertices is reserved upfront, the labels are hashed only once,
+    /// keep returned ids to avoid hashing them again
+    ///
+    /// ```
+    /// use simple_graph::{Graph, GraphOperationError};
+    ///
+    /// let mut graph: Graph<usize, u32> = Graph::new();
+    /// let ids = graph.extend_vertices(1..=3).unwrap();
+    ///
+    /// graph.add_edge(ids[0], ids[2], 7).unwrap();
+    /// assert_eq!(graph.get_edge_value(graph.get_vertex_id(&1), graph.get_vertex_id(&3)), Ok(&7));
+    ///
+    /// assert_eq!(graph.extend_vertices([4, 5, 3]), Err(GraphOperationError::VertexAlreadyExists));
+    /// assert_eq!(graph.extend_vertices([6, 7, 6]), Err(GraphOperationError::VertexAlreadyExists));
+    /// assert_eq!(graph.vertices_count(), 3);
+    /// ```
+    pub fn extend_vertices<I: IntoIterator<Item = V>>(
+        &mut self,
+        vertices: I,
+    ) -> Result<Vec<VertexId>> {
+        let vertices = vertices
+            .into_iter()
+            .map(|vertex| (self.get_vertex_id(&vertex), vertex))
+            .collect::<Vec<_>>();
+
+        let mut ids = HashSet::with_capacity(vertices.len());
+        for (vertex_id, _) in &vertices {
+            if self.vertices.contains_key(vertex_id) || !ids.insert(*vertex_id) {
+                return Err(GraphOperationError::VertexAlreadyExists);
+            }
+        }
+
+        self.vertices.reserve(vertices.len());
+        self.vertices_data.reserve(vertices.len());
+        Ok(vertices
+            .into_iter()
+            .map(|(vertex_id, vertex)| {
+                self.insert_vertex(vertex_id, vertex);
+                vertex_id
+            })
+            .collect())
+    }
+
     /// Trying to get vertex by id, returns [`GraphOperationError::VertexDoesNotExist`]
     /// if can't do this
     ///
@@ -175,8 +254,17 @@ impl<V: Label, E: Label> Graph<V, E> {
             }
         }
 
-        self.vertices.remove(&target_vertex);
-        self.vertices_data.remove(&target_vertex);
+        let outgoing = self.vertices.remove(&target_vertex).unwrap_or_default();
+        if let Some(vertex) = self.vertices_data.remove(&target_vertex) {
+            self.observers.notify(|| GraphEvent::VertexRemoved {
+                vertex_id: target_vertex,
+                vertex,
+                edges: outgoing
+                    .into_iter()
+                    .chain(pairs.into_iter().map(|(_, edge)| edge))
+                    .collect(),
+            });
+        }
 
         Ok(())
     }
@@ -201,7 +289,11 @@ impl<V: Label, E: Label> Graph<V, E> {
         if self.vertices.get(&to).is_some()
             && let Some(neighbours) = self.vertices.get_mut(&from)
         {
-            neighbours.insert(([from, to], edge));
+            // the same edge is only moved after other edges of the vertex
+            if neighbours.insert(([from, to], edge.clone())) {
+                self.observers
+                    .notify(|| GraphEvent::EdgeAdded { from, to, edge });
+            }
             return Ok(());
         }
         Err(GraphOperationError::VertexDoesNotExist)
@@ -276,11 +368,220 @@ impl<V: Label, E: Label> Graph<V, E> {
             && let Some(neighbours) = self.vertices.get_mut(&from)
         {
             neighbours.remove(&edge);
+            self.observers.notify(|| GraphEvent::EdgeRemoved {
+                from,
+                to,
+                edge: edge.1,
+            });
             return Ok(());
         }
         Err(GraphOperationError::EdgeDoesNotExist)
     }
 
+    /// Removes the edge with exactly this label, returns `false` if it doesn't exist
+    pub(crate) fn remove_labelled_edge(&mut self, from: VertexId, to: VertexId, edge: &E) -> bool {
+        let edge = ([from, to], edge.clone());
+        let removed = self
+            .vertices
+            .get_mut(&from)
+            .is_some_and(|neighbours| neighbours.remove(&edge));
+        if removed {
+            self.observers.notify(|| GraphEvent::EdgeRemoved {
+                from,
+                to,
+                edge: edge.1,
+            });
+        }
+        removed
+    }
+
+    /// Replaces label of the edge between two vertices and returns the previous one,
+    /// returns [`GraphOperationError::EdgeDoesNotExist`] if can't do this
+    ///
+    /// The edge keeps its position among edges of the vertex. If there are parallel edges,
+    /// the same edge as in [`Graph::get_edge`] is updated. If a parallel edge already has
+    /// the new label, the updated edge is merged with it, so the count of edges decreases
+    /// and observers get [`GraphEvent::EdgeRemoved`] after [`GraphEvent::EdgeUpdated`]
+    ///
+    /// ```
+    /// use simple_graph::{Graph, GraphEvent, GraphOperationError};
+    /// use std::str::FromStr;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let mut graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    ///
+    /// let moscow = graph.get_vertex_id(&"Moscow".into());
+    /// let vladimir = graph.get_vertex_id(&"Vladimir".into());
+    ///
+    /// assert_eq!(graph.update_edge(moscow, vladimir, 190), Ok(180));
+    /// assert_eq!(graph.get_edge_value(moscow, vladimir), Ok(&190));
+    /// assert_eq!(graph.update_edge(vladimir, moscow, 190), Err(GraphOperationError::EdgeDoesNotExist));
+    ///
+    /// // parallel edge with the same label is merged
+    /// graph.add_edge(moscow, vladimir, 200).unwrap();
+    /// let events = Arc::new(Mutex::new(Vec::new()));
+    /// let sink = events.clone();
+    /// graph.subscribe(move |event: &GraphEvent<String, u32>| sink.lock().unwrap().push(event.clone()));
+    ///
+    /// assert_eq!(graph.update_edge(moscow, vladimir, 200), Ok(190));
+    /// assert_eq!(graph.edges_count(), 4);
+    /// assert_eq!(
+    ///     events.lock().unwrap()[1],
+    ///     GraphEvent::EdgeRemoved { from: moscow, to: vladimir, edge: 200 }
+    /// );
+    /// ```
+    pub fn update_edge(&mut self, from: VertexId, to: VertexId, edge: E) -> Result<E> {
+        let old = self.get_edge(from, to)?.clone();
+        let new = ([from, to], edge);
+        if old == new {
+            return Ok(new.1);
+        }
+        let mut merged = false;
+        if let Some(neighbours) = self.vertices.get_mut(&from) {
+            // parallel edge with the new label is merged with the updated one
+            merged = neighbours.contains(&new);
+            let edges = std::mem::take(neighbours);
+            for e in edges {
+                neighbours.insert_if_absent(if e == old { new.clone() } else { e });
+            }
+        }
+        self.observers.notify(|| GraphEvent::EdgeUpdated {
+            from,
+            to,
+            old: old.1.clone(),
+            new: new.1.clone(),
+        });
+        if merged {
+            self.observers.notify(|| GraphEvent::EdgeRemoved {
+                from,
+                to,
+                edge: new.1,
+            });
+        }
+        Ok(old.1)
+    }
+
+    /// Registers the observer which is called after every change of the graph,
+    /// e.g. to keep external indexes in sync with it
+    ///
+    /// Observers are called by [`Graph::add_vertex`], [`Graph::remove_vertex`],
+    /// [`Graph::add_edge`], [`Graph::remove_edge`], [`Graph::update_edge`] and other methods
+    /// which use them. Clones of the graph don't have observers
+    ///
+    /// ```
+    /// use simple_graph::{Graph, GraphEvent};
+    /// use std::str::FromStr;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let mut graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let events = Arc::new(Mutex::new(Vec::new()));
+    /// let sink = events.clone();
+    /// graph.subscribe(move |event: &GraphEvent<String, u32>| sink.lock().unwrap().push(event.clone()));
+    ///
+    /// let moscow = graph.get_vertex_id(&"Moscow".into());
+    /// let vladimir = graph.get_vertex_id(&"Vladimir".into());
+    /// let kazan = graph.add_vertex("Kazan".into()).unwrap();
+    /// graph.add_edge(kazan, vladimir, 450).unwrap();
+    /// graph.remove_vertex(vladimir).unwrap();
+    ///
+    /// let events = events.lock().unwrap();
+    /// assert_eq!(events.len(), 3);
+    /// assert_eq!(events[0], GraphEvent::VertexAdded { vertex_id: kazan, vertex: "Kazan".into() });
+    /// assert_eq!(events[1], GraphEvent::EdgeAdded { from: kazan, to: vladimir, edge: 450 });
+    /// let GraphEvent::VertexRemoved { vertex, edges, .. } = &events[2] else {
+    ///     panic!("expected removed vertex");
+    /// };
+    /// assert_eq!(vertex, "Vladimir");
+    /// // outgoing edge to Novgorod and incoming edges from Moscow and Kazan
+    /// assert_eq!(edges.len(), 3);
+    /// assert!(edges.contains(&([moscow, vladimir], 180)));
+    /// ```
+    pub fn subscribe<F>(&mut self, observer: F) -> ObserverId
+    where
+        F: FnMut(&GraphEvent<V, E>) + Send + Sync + 'static,
+    {
+        self.observers.add(Box::new(observer))
+    }
+
+    /// Removes the observer, returns `false` if it's already removed
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    ///
+    /// let mut graph: Graph<String, u32> = Graph::new();
+    /// let observer = graph.subscribe(|_| panic!("observer is removed"));
+    ///
+    /// assert!(graph.unsubscribe(observer));
+    /// assert!(!graph.unsubscribe(observer));
+    /// graph.add_vertex("Moscow".into()).unwrap();
+    /// ```
+    pub fn unsubscribe(&mut self, observer: ObserverId) -> bool {
+        self.observers.remove(observer)
+    }
+
+    /// Adds all vertices and edges of `other` graph into this one, vertices with the same data
+    /// are the same vertex
+    ///
+    /// If vertex exists in both graphs [`DuplicateVertexPolicy::Error`] returns
+    /// [`GraphOperationError::VertexAlreadyExists`] and the graph stays unchanged. Otherwise edges
+    /// are joined, and if both graphs have an edge between the same vertices the policy decides
+    /// which label is kept.
+    ///
+    /// ```
+    /// use simple_graph::{DuplicateVertexPolicy, Graph, GraphOperationError};
+    /// use std::str::FromStr;
+    ///
+    /// let mut graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let other: Graph<String, u32> = Graph::from_str("1 Moscow\n2 Vladimir\n3 Kazan\n#\n1 2 190\n2 3 520\n").unwrap();
+    ///
+    /// assert_eq!(
+    ///     graph.merge(&other, DuplicateVertexPolicy::Error),
+    ///     Err(GraphOperationError::VertexAlreadyExists)
+    /// );
+    /// graph.merge(&other, DuplicateVertexPolicy::KeepFirst).unwrap();
+    ///
+    /// let moscow = graph.get_vertex_id(&"Moscow".into());
+    /// let vladimir = graph.get_vertex_id(&"Vladimir".into());
+    /// let kazan = graph.get_vertex_id(&"Kazan".into());
+    ///
+    /// assert_eq!(graph.vertices_count(), 6);
+    /// assert_eq!(graph.get_edge_value(moscow, vladimir), Ok(&180));
+    /// assert_eq!(graph.get_edge_value(vladimir, kazan), Ok(&520));
+    ///
+    /// graph.merge(&other, DuplicateVertexPolicy::KeepLast).unwrap();
+    /// assert_eq!(graph.get_edge_value(moscow, vladimir), Ok(&190));
+    /// assert_eq!(graph.edges_count(), 5);
+    /// ```
+    pub fn merge(&mut self, other: &Graph<V, E>, policy: DuplicateVertexPolicy) -> Result<()> {
+        if policy == DuplicateVertexPolicy::Error
+            && other
+                .vertices
+                .keys()
+                .any(|id| self.vertices.contains_key(id))
+        {
+            return Err(GraphOperationError::VertexAlreadyExists);
+        }
+
+        for id in other.vertices.keys() {
+            if !self.vertices.contains_key(id) {
+                self.add_vertex(other.get_vertex(*id)?.clone())?;
+            }
+        }
+
+        for neighbours in other.vertices.values() {
+            for ([from, to], edge) in neighbours {
+                if self.get_edge(*from, *to).is_ok() {
+                    if policy == DuplicateVertexPolicy::KeepLast {
+                        self.update_edge(*from, *to, edge.clone())?;
+                    }
+                    continue;
+                }
+                self.add_edge(*from, *to, edge.clone())?;
+            }
+        }
+        Ok(())
+    }
+
     /// Returns count of vertices in the graph
     ///
     /// ```
@@ -403,44 +704,7 @@ impl<V: Label, E: Label> Graph<V, E> {
         Ok((vertex, adjacent_vertices))
     }
 
-    /// Generic search algorithm for implementation BFS and DFS.
-    /// It uses generic queue type and queue operations to prevent code duplication
-    fn generic_search<
-        QueueTy,
-        QueueInitFn: Fn() -> QueueTy,
-        QueueInsertFn: Fn(&mut QueueTy, VertexId),
-        QueueRemoveFn: Fn(&mut QueueTy) -> Option<VertexId>,
-        AccessFn: FnMut(&V, Vec<(&V, &E)>),
-    >(
-        &self,
-        queue_init: QueueInitFn,
-        queue_insert: QueueInsertFn,
-        queue_remove: QueueRemoveFn,
-        source: VertexId,
-        mut access: AccessFn,
-    ) -> Result<()> {
-        let mut queue = queue_init();
-        let mut visited = HashSet::new();
-
-        queue_insert(&mut queue, source);
-        visited.insert(source);
-
-        while let Some(vertex) = queue_remove(&mut queue) {
-            let (vertex, adjacent_vertices) = self.get_vertex_info(vertex)?;
-            for (adjacent_vertex, _) in adjacent_vertices.iter() {
-                let id = self.get_vertex_id(adjacent_vertex);
-                if !visited.contains(&id) {
-                    queue_insert(&mut queue, id);
-                    visited.insert(id);
-                }
-            }
-            access(vertex, adjacent_vertices);
-        }
-
-        Ok(())
-    }
-
-    /// Breadth-first search algorithm uses [`VecDeque`] for queue
+    /// Breadth-first search algorithm, see [`crate::bfs`]
     ///
     /// ```
     /// use simple_graph::Graph;
@@ -471,16 +735,10 @@ impl<V: Label, E: Label> Graph<V, E> {
     /// assert_eq!(visited, expected);
     /// ```
     pub fn bfs<F: FnMut(&V, Vec<(&V, &E)>)>(&self, source: VertexId, access: F) -> Result<()> {
-        self.generic_search(
-            VecDeque::new,
-            |queue, vertex| queue.push_back(vertex),
-            |queue| queue.pop_front(),
-            source,
-            access,
-        )
+        traits::bfs(self, source, access)
     }
 
-    /// Depth-first search algorithm uses [`Vec`] for stack
+    /// Depth-first search algorithm, see [`crate::dfs`]
     ///
     /// ```
     /// use simple_graph::Graph;
@@ -511,12 +769,54 @@ impl<V: Label, E: Label> Graph<V, E> {
     /// assert_eq!(visited, expected);
     /// ```
     pub fn dfs<F: FnMut(&V, Vec<(&V, &E)>)>(&self, source: VertexId, access: F) -> Result<()> {
-        self.generic_search(
-            Vec::new,
-            |stack, vertex| stack.push(vertex),
-            |stack| stack.pop(),
-            source,
-            access,
-        )
+        traits::dfs(self, source, access)
+    }
+}
+
+impl<V: Label, E: Label> GraphBase for Graph<V, E> {
+    type VertexId = VertexId;
+    type Vertex = V;
+    type Edge = E;
+
+    fn vertex(&self, vertex_id: VertexId) -> Result<&V> {
+        self.get_vertex(vertex_id)
+    }
+}
+
+impl<V: Label, E: Label> VertexCount for Graph<V, E> {
+    fn vertices_count(&self) -> usize {
+        Graph::vertices_count(self)
+    }
+}
+
+impl<V: Label, E: Label> Vertices for Graph<V, E> {
+    fn vertex_ids(&self) -> impl Iterator<Item = VertexId> {
+        self.vertices.keys().copied()
+    }
+}
+
+impl<V: Label, E: Label> Neighbors for Graph<V, E> {
+    fn neighbors(&self, vertex_id: VertexId) -> Result<impl Iterator<Item = (VertexId, &E)>> {
+        let neighbours = self
+            .vertices
+            .get(&vertex_id)
+            .ok_or(GraphOperationError::VertexDoesNotExist)?;
+        Ok(neighbours.iter().map(|([_, to], edge)| (*to, edge)))
+    }
+}
+
+impl<V: Label, E: Label> EdgeWeights for Graph<V, E> {
+    fn edge_weight(&self, from: VertexId, to: VertexId) -> Result<&E> {
+        self.get_edge_value(from, to)
+    }
+}
+
+impl<V: Label, E: Label> Build for Graph<V, E> {
+    fn add_vertex(&mut self, vertex: V) -> Result<VertexId> {
+        Graph::add_vertex(self, vertex)
+    }
+
+    fn add_edge(&mut self, from: VertexId, to: VertexId, edge: E) -> Result<()> {
+        Graph::add_edge(self, from, to, edge)
     }
 }
@@ -0,0 +1,78 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::{Graph, Label, TgfLayout, TgfOrder, escape_xml};
+
+/// Formats [`Graph<V, E>`] in [GraphML](http://graphml.graphdrawing.org) format
+///
+/// Vertices get ids `n1`, `n2`, ... in insertion order,
+/// labels of the vertices and edges are exported as `label` data
+///
+/// ```
+/// use simple_graph::{Graph, GraphMl};
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, String> =
+///     Graph::from_str(include_str!("../test_input/default.tgf")).unwrap();
+///
+/// let expected = concat!(
+///     "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+///     "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+///     "  <key id=\"vertex_label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+///     "  <key id=\"edge_label\" for=\"edge\" attr.name=\"label\" attr.type=\"string\"/>\n",
+///     "  <graph edgedefault=\"directed\">\n",
+///     "    <node id=\"n1\">\n",
+///     "      <data key=\"vertex_label\">First node</data>\n",
+///     "    </node>\n",
+///     "    <node id=\"n2\">\n",
+///     "      <data key=\"vertex_label\">Second node</data>\n",
+///     "    </node>\n",
+///     "    <edge source=\"n1\" target=\"n2\">\n",
+///     "      <data key=\"edge_label\">Edge between the two</data>\n",
+///     "    </edge>\n",
+///     "  </graph>\n",
+///     "</graphml>\n",
+/// );
+/// assert_eq!(GraphMl(&graph).to_string(), expected);
+/// ```
+pub struct GraphMl<'a, V: Label, E: Label>(pub &'a Graph<V, E>);
+
+impl<V: Label, E: Label> Display for GraphMl<'_, V, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let layout = TgfLayout::new(self.0, None, TgfOrder::Insertion).map_err(|_| fmt::Error)?;
+
+        writeln!(f, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(
+            f,
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">"
+        )?;
+        writeln!(
+            f,
+            "  <key id=\"vertex_label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>"
+        )?;
+        writeln!(
+            f,
+            "  <key id=\"edge_label\" for=\"edge\" attr.name=\"label\" attr.type=\"string\"/>"
+        )?;
+        writeln!(f, "  <graph edgedefault=\"directed\">")?;
+        for (index, vertex) in layout.vertices {
+            writeln!(f, "    <node id=\"n{index}\">")?;
+            writeln!(
+                f,
+                "      <data key=\"vertex_label\">{}</data>",
+                escape_xml(&vertex.to_string())
+            )?;
+            writeln!(f, "    </node>")?;
+        }
+        for (from, to, edge) in layout.edges {
+            writeln!(f, "    <edge source=\"n{from}\" target=\"n{to}\">")?;
+            writeln!(
+                f,
+                "      <data key=\"edge_label\">{}</data>",
+                escape_xml(&edge.to_string())
+            )?;
+            writeln!(f, "    </edge>")?;
+        }
+        writeln!(f, "  </graph>")?;
+        writeln!(f, "</graphml>")
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt::{self, Display, Formatter, Write};
+
+use super::{Graph, Label, TgfLayout, TgfOrder};
+
+/// Formats [`Graph<V, E>`] as JSON object with `vertices` and `edges` arrays
+///
+/// Vertices are numbered from 1 in insertion order, labels are written as strings
+///
+/// ```
+/// use simple_graph::{Graph, Json};
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, String> =
+///     Graph::from_str(include_str!("../test_input/default.tgf")).unwrap();
+///
+/// let expected = concat!(
+///     "{\n",
+///     "  \"vertices\": [\n",
+///     "    {\"id\": 1, \"label\": \"First node\"},\n",
+///     "    {\"id\": 2, \"label\": \"Second node\"}\n",
+///     "  ],\n",
+///     "  \"edges\": [\n",
+///     "    {\"source\": 1, \"target\": 2, \"label\": \"Edge between the two\"}\n",
+///     "  ]\n",
+///     "}\n",
+/// );
+/// assert_eq!(Json(&graph).to_string(), expected);
+/// ```
+pub struct Json<'a, V: Label, E: Label>(pub &'a Graph<V, E>);
+
+/// Formats string as JSON string literal with quotes
+///
+/// ```
+/// use simple_graph::escape_json;
+///
+/// assert_eq!(escape_json("say \"hi\"\n"), r#""say \"hi\"\n""#);
+/// ```
+pub fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+impl<V: Label, E: Label> Display for Json<'_, V, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let layout = TgfLayout::new(self.0, None, TgfOrder::Insertion).map_err(|_| fmt::Error)?;
+        let separator = |n: usize, len: usize| if n + 1 < len { "," } else { "" };
+
+        writeln!(f, "{{")?;
+        writeln!(f, "  \"vertices\": [")?;
+        let len = layout.vertices.len();
+        for (n, (index, vertex)) in layout.vertices.into_iter().enumerate() {
+            let label = escape_json(&vertex.to_string());
+            writeln!(
+                f,
+                "    {{\"id\": {index}, \"label\": {label}}}{}",
+                separator(n, len)
+            )?;
+        }
+        writeln!(f, "  ],")?;
+        writeln!(f, "  \"edges\": [")?;
+        let len = layout.edges.len();
+        for (n, (from, to, edge)) in layout.edges.into_iter().enumerate() {
+            let label = escape_json(&edge.to_string());
+            writeln!(
+                f,
+                "    {{\"source\": {from}, \"target\": {to}, \"label\": {label}}}{}",
+                separator(n, len)
+            )?;
+        }
+        writeln!(f, "  ]")?;
+        writeln!(f, "}}")
+    }
+}
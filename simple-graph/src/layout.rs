@@ -0,0 +1,315 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::f64::consts::TAU;
+
+use super::{Graph, Label, VertexId};
+
+/// Horizontal distance between neighbour vertices of the same layer in [`LayoutAlgorithm::Layered`]
+pub(crate) const HORIZONTAL_SPACING: f64 = 160.0;
+/// Vertical distance between layers in [`LayoutAlgorithm::Layered`]
+pub(crate) const VERTICAL_SPACING: f64 = 100.0;
+/// Preferred length of the edge in [`LayoutAlgorithm::ForceDirected`]
+const IDEAL_DISTANCE: f64 = 150.0;
+/// Number of simulation steps in [`LayoutAlgorithm::ForceDirected`]
+const ITERATIONS: usize = 300;
+/// Pull towards the center which keeps disconnected components close to each other
+const GRAVITY: f64 = 0.05;
+/// Number of barycenter sweeps used to reduce crossings in [`LayoutAlgorithm::Layered`]
+const ORDERING_SWEEPS: usize = 4;
+
+/// Point on the plane, `y` grows downwards like in SVG
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Point {
+    /// horizontal coordinate
+    pub x: f64,
+    /// vertical coordinate
+    pub y: f64,
+}
+
+/// Algorithm which places vertices on the plane, see [`Graph::layout`]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum LayoutAlgorithm {
+    /// [`LayoutAlgorithm::Layered`] for directed acyclic graphs,
+    /// [`LayoutAlgorithm::ForceDirected`] for everything else
+    #[default]
+    Auto,
+    /// Sugiyama-style layout where every edge goes downwards if possible,
+    /// edges which make cycles are drawn upwards
+    Layered,
+    /// Fruchterman-Reingold simulation where edges pull vertices together
+    /// and all vertices push each other apart
+    ForceDirected,
+}
+
+/// Positions of the vertices computed by [`Graph::layout`]
+///
+/// Coordinates are non-negative, the leftmost and the topmost vertices have zero coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout {
+    /// center of every vertex
+    pub positions: HashMap<VertexId, Point>,
+    /// largest `x` among the vertices
+    pub width: f64,
+    /// largest `y` among the vertices
+    pub height: f64,
+}
+
+impl Layout {
+    fn new(ids: Vec<VertexId>, points: Vec<Point>) -> Self {
+        let min_x = points.iter().map(|p| p.x).fold(f64::INFINITY, f64::min);
+        let min_y = points.iter().map(|p| p.y).fold(f64::INFINITY, f64::min);
+        let positions = ids
+            .into_iter()
+            .zip(points)
+            .map(|(vertex_id, p)| {
+                let point = Point {
+                    x: p.x - min_x,
+                    y: p.y - min_y,
+                };
+                (vertex_id, point)
+            })
+            .collect::<HashMap<_, _>>();
+        let width = positions.values().map(|p| p.x).fold(0.0, f64::max);
+        let height = positions.values().map(|p| p.y).fold(0.0, f64::max);
+        Self {
+            positions,
+            width,
+            height,
+        }
+    }
+}
+
+impl<V: Label, E: Label> Graph<V, E> {
+    /// Places vertices of the graph on the plane using specified algorithm,
+    /// result is deterministic for the same graph
+    ///
+    /// ```
+    /// use simple_graph::{Graph, LayoutAlgorithm};
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> =
+    ///     Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let layout = graph.layout(LayoutAlgorithm::Layered);
+    ///
+    /// let position = |label: &str| layout.positions[&graph.get_vertex_id(&label.into())];
+    /// assert_eq!(position("Moscow").y, 0.0);
+    /// assert!(position("Vladimir").y > position("Moscow").y);
+    /// assert!(position("Novgorod").y > position("Vladimir").y);
+    /// ```
+    pub fn layout(&self, algorithm: LayoutAlgorithm) -> Layout {
+        let ids = self.vertices.keys().copied().collect::<Vec<_>>();
+        let indices = ids
+            .iter()
+            .enumerate()
+            .map(|(index, &vertex_id)| (vertex_id, index))
+            .collect::<HashMap<_, _>>();
+        // self-loops don't affect positions of the vertices
+        let successors = self
+            .vertices
+            .values()
+            .enumerate()
+            .map(|(from, neighbours)| {
+                neighbours
+                    .iter()
+                    .map(|([_, to], _)| indices[to])
+                    .filter(|&to| to != from)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let back = back_edges(&successors);
+        let points = match algorithm {
+            LayoutAlgorithm::Layered => layered(&successors, &back),
+            LayoutAlgorithm::Auto if back.is_empty() => layered(&successors, &back),
+            _ => force_directed(&successors),
+        };
+        Layout::new(ids, points)
+    }
+}
+
+/// Finds edges which go back to the vertex on the current path of depth-first search,
+/// graph becomes acyclic if all of them are reversed
+fn back_edges(successors: &[Vec<usize>]) -> HashSet<(usize, usize)> {
+    #[derive(Copy, Clone, Eq, PartialEq)]
+    enum State {
+        New,
+        Active,
+        Done,
+    }
+
+    let mut state = vec![State::New; successors.len()];
+    let mut back = HashSet::new();
+    for root in 0..successors.len() {
+        if state[root] != State::New {
+            continue;
+        }
+        state[root] = State::Active;
+        let mut stack = vec![(root, 0)];
+        while let Some((vertex, next)) = stack.last_mut() {
+            let vertex = *vertex;
+            let to = successors[vertex].get(*next).copied();
+            *next += 1;
+            match to {
+                None => {
+                    state[vertex] = State::Done;
+                    stack.pop();
+                }
+                Some(to) => match state[to] {
+                    State::New => {
+                        state[to] = State::Active;
+                        stack.push((to, 0));
+                    }
+                    State::Active => {
+                        back.insert((vertex, to));
+                    }
+                    State::Done => {}
+                },
+            }
+        }
+    }
+    back
+}
+
+fn layered(successors: &[Vec<usize>], back: &HashSet<(usize, usize)>) -> Vec<Point> {
+    let n = successors.len();
+    let mut outgoing = vec![Vec::new(); n];
+    let mut incoming = vec![Vec::new(); n];
+    for (from, targets) in successors.iter().enumerate() {
+        for &to in targets {
+            let (from, to) = match back.contains(&(from, to)) {
+                true => (to, from),
+                false => (from, to),
+            };
+            outgoing[from].push(to);
+            incoming[to].push(from);
+        }
+    }
+
+    // longest path layering in topological order
+    let mut layer = vec![0_usize; n];
+    let mut degree = incoming.iter().map(Vec::len).collect::<Vec<_>>();
+    let mut queue = (0..n).filter(|&v| degree[v] == 0).collect::<VecDeque<_>>();
+    while let Some(vertex) = queue.pop_front() {
+        for &to in &outgoing[vertex] {
+            layer[to] = layer[to].max(layer[vertex] + 1);
+            degree[to] -= 1;
+            if degree[to] == 0 {
+                queue.push_back(to);
+            }
+        }
+    }
+
+    let depth = layer.iter().max().map_or(0, |&max| max + 1);
+    let mut layers = vec![Vec::new(); depth];
+    for vertex in 0..n {
+        layers[layer[vertex]].push(vertex);
+    }
+
+    // barycenter heuristic, vertex moves towards average position of its neighbours
+    let mut position = vec![0.0; n];
+    for vertices in &layers {
+        for (index, &vertex) in vertices.iter().enumerate() {
+            position[vertex] = index as f64;
+        }
+    }
+    let reorder = |vertices: &mut Vec<usize>, neighbours: &[Vec<usize>], position: &mut [f64]| {
+        let keys = vertices
+            .iter()
+            .map(|&vertex| {
+                let adjacent = &neighbours[vertex];
+                match adjacent.is_empty() {
+                    true => position[vertex],
+                    false => {
+                        adjacent.iter().map(|&v| position[v]).sum::<f64>() / adjacent.len() as f64
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut order = (0..vertices.len()).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| keys[a].total_cmp(&keys[b]));
+        *vertices = order.into_iter().map(|i| vertices[i]).collect();
+        for (index, &vertex) in vertices.iter().enumerate() {
+            position[vertex] = index as f64;
+        }
+    };
+    for _ in 0..ORDERING_SWEEPS {
+        for vertices in layers.iter_mut().skip(1) {
+            reorder(vertices, &incoming, &mut position);
+        }
+        for vertices in layers.iter_mut().rev().skip(1) {
+            reorder(vertices, &outgoing, &mut position);
+        }
+    }
+
+    let widest = layers.iter().map(Vec::len).max().unwrap_or(0);
+    let mut points = vec![Point::default(); n];
+    for (l, vertices) in layers.iter().enumerate() {
+        let shift = (widest - vertices.len()) as f64 / 2.0;
+        for (index, &vertex) in vertices.iter().enumerate() {
+            points[vertex] = Point {
+                x: (index as f64 + shift) * HORIZONTAL_SPACING,
+                y: l as f64 * VERTICAL_SPACING,
+            };
+        }
+    }
+    points
+}
+
+fn force_directed(successors: &[Vec<usize>]) -> Vec<Point> {
+    let n = successors.len();
+    let k = IDEAL_DISTANCE;
+    let radius = (k * n as f64 / TAU).max(k);
+    // vertices start on the circle, so the result doesn't depend on random numbers
+    let mut points = (0..n)
+        .map(|i| {
+            let angle = TAU * i as f64 / n as f64;
+            Point {
+                x: radius * angle.cos(),
+                y: radius * angle.sin(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    for step in 0..ITERATIONS {
+        let mut shift = points
+            .iter()
+            .map(|p| Point {
+                x: -p.x * GRAVITY,
+                y: -p.y * GRAVITY,
+            })
+            .collect::<Vec<_>>();
+
+        for i in 0..n {
+            for j in i + 1..n {
+                let (dx, dy) = (points[i].x - points[j].x, points[i].y - points[j].y);
+                let distance = dx.hypot(dy).max(0.01);
+                let force = k * k / distance / distance;
+                shift[i].x += dx * force;
+                shift[i].y += dy * force;
+                shift[j].x -= dx * force;
+                shift[j].y -= dy * force;
+            }
+        }
+        for (from, targets) in successors.iter().enumerate() {
+            for &to in targets {
+                let (dx, dy) = (points[from].x - points[to].x, points[from].y - points[to].y);
+                let force = dx.hypot(dy) / k;
+                shift[from].x -= dx * force;
+                shift[from].y -= dy * force;
+                shift[to].x += dx * force;
+                shift[to].y += dy * force;
+            }
+        }
+
+        // movement is limited by temperature which decreases every step
+        let temperature = radius * (1.0 - step as f64 / ITERATIONS as f64) / 2.0;
+        for (point, shift) in points.iter_mut().zip(&shift) {
+            let length = shift.x.hypot(shift.y);
+            if length > 0.0 {
+                let scale = length.min(temperature) / length;
+                point.x += shift.x * scale;
+                point.y += shift.y * scale;
+            }
+        }
+    }
+    points
+}
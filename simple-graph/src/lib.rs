@@ -8,10 +8,66 @@
 
 #![feature(str_split_whitespace_remainder)]
 
+pub use binary::*;
+pub use diagnostic::*;
+pub use diagram::*;
+pub use diff::*;
+pub use dimacs::*;
+pub use dot::*;
 pub use error::*;
+pub use frozen::*;
+pub use gexf::*;
+pub use gml::*;
 pub use graph::*;
+pub use graphml::*;
+pub use json::*;
+pub use layout::*;
+pub use matrix::*;
+pub use matrix_market::*;
+pub use metis::*;
+pub use observer::*;
+pub use pajek::*;
+pub use persistent::*;
+pub use shortest_path::*;
+pub use stats::*;
+pub use svg::*;
+pub use terminal::*;
 pub use tgf::*;
+pub use tgf_document::*;
+pub use tgf_options::*;
+pub use tgf_reader::*;
+pub use tgf_writer::*;
+pub use traits::*;
+pub use transaction::*;
 
+mod binary;
+mod diagnostic;
+mod diagram;
+mod diff;
+mod dimacs;
+mod dot;
 mod error;
+mod frozen;
+mod gexf;
+mod gml;
 mod graph;
+mod graphml;
+mod json;
+mod layout;
+mod matrix;
+mod matrix_market;
+mod metis;
+mod observer;
+mod pajek;
+mod persistent;
+mod shortest_path;
+mod stats;
+mod svg;
+mod terminal;
 mod tgf;
+mod tgf_document;
+mod tgf_options;
+mod tgf_reader;
+mod tgf_writer;
+mod traits;
+mod transaction;
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use super::{Graph, GraphOperationError, Label, Result, VertexId};
+
+/// Dense adjacency matrix of the [`Graph<V, E>`], see [`Graph::to_adjacency_matrix`]
+///
+/// Row is the source of the edge and column is the target,
+/// absent edges are filled with `T::default()`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AdjacencyMatrix<T> {
+    /// ids of the vertices, position of the id is its index in the matrix
+    pub vertices: Vec<VertexId>,
+    /// index of every vertex in the matrix
+    pub indices: HashMap<VertexId, usize>,
+    /// `vertices.len() × vertices.len()` matrix of the numbers
+    pub matrix: Vec<Vec<T>>,
+}
+
+/// Adjacency list of the [`Graph<V, E>`] in Compressed Sparse Row format, see [`Graph::to_csr`]
+///
+/// Targets and values of the edges going from vertex with index `i`
+/// are stored in `columns[offsets[i]..offsets[i + 1]]` and `values[offsets[i]..offsets[i + 1]]`
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Csr<T> {
+    /// ids of the vertices, position of the id is its index in the rows and columns
+    pub vertices: Vec<VertexId>,
+    /// index of every vertex in the rows and columns
+    pub indices: HashMap<VertexId, usize>,
+    /// `vertices.len() + 1` offsets of the rows in `columns` and `values`
+    pub offsets: Vec<usize>,
+    /// indices of the edge targets
+    pub columns: Vec<usize>,
+    /// numbers of the edges
+    pub values: Vec<T>,
+}
+
+impl<T> Csr<T> {
+    /// Returns `(target, value)` pairs of the edges going from vertex with index `row`
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> =
+    ///     Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let csr = graph.to_csr(|&distance| distance);
+    ///
+    /// let moscow = csr.indices[&graph.get_vertex_id(&"Moscow".into())];
+    /// let neighbours = csr.row(moscow).map(|(to, &distance)| distance).collect::<Vec<_>>();
+    /// assert_eq!(neighbours, [180, 250]);
+    /// ```
+    pub fn row(&self, row: usize) -> impl Iterator<Item = (usize, &T)> {
+        let range = self.offsets[row]..self.offsets[row + 1];
+        self.columns[range.clone()]
+            .iter()
+            .copied()
+            .zip(&self.values[range])
+    }
+}
+
+impl<V: Label, E: Label> Graph<V, E> {
+    /// Returns ids of the vertices in insertion order and mapping from ids to their positions
+    fn index_vertices(&self) -> (Vec<VertexId>, HashMap<VertexId, usize>) {
+        let vertices = self.vertices.keys().copied().collect::<Vec<_>>();
+        let indices = vertices
+            .iter()
+            .enumerate()
+            .map(|(index, &vertex_id)| (vertex_id, index))
+            .collect();
+        (vertices, indices)
+    }
+
+    /// Converts graph into dense adjacency matrix, `weight` maps label of every edge into number
+    ///
+    /// Vertices are indexed in insertion order
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> =
+    ///     Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let adjacency = graph.to_adjacency_matrix(|&distance| f64::from(distance));
+    ///
+    /// let moscow = adjacency.indices[&graph.get_vertex_id(&"Moscow".into())];
+    /// let vladimir = adjacency.indices[&graph.get_vertex_id(&"Vladimir".into())];
+    /// assert_eq!(adjacency.matrix[moscow][vladimir], 180.0);
+    /// assert_eq!(adjacency.matrix[vladimir][moscow], 0.0);
+    /// ```
+    pub fn to_adjacency_matrix<T: Clone + Default>(
+        &self,
+        weight: impl Fn(&E) -> T,
+    ) -> AdjacencyMatrix<T> {
+        let (vertices, indices) = self.index_vertices();
+        let mut matrix = vec![vec![T::default(); vertices.len()]; vertices.len()];
+        for (row, neighbours) in matrix.iter_mut().zip(self.vertices.values()) {
+            for ([_, to], edge) in neighbours {
+                row[indices[to]] = weight(edge);
+            }
+        }
+        AdjacencyMatrix {
+            vertices,
+            indices,
+            matrix,
+        }
+    }
+
+    /// Converts graph into adjacency list in Compressed Sparse Row format,
+    /// `weight` maps label of every edge into number
+    ///
+    /// Vertices are indexed in insertion order, edges in rows keep insertion order too
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> =
+    ///     Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let csr = graph.to_csr(|&distance| distance);
+    ///
+    /// assert_eq!(csr.offsets, [0, 2, 3, 4, 4, 4]);
+    /// assert_eq!(csr.columns, [1, 2, 3, 4]);
+    /// assert_eq!(csr.values, [180, 250, 225, 175]);
+    /// ```
+    pub fn to_csr<T>(&self, weight: impl Fn(&E) -> T) -> Csr<T> {
+        let (vertices, indices) = self.index_vertices();
+        let mut offsets = Vec::with_capacity(vertices.len() + 1);
+        let mut columns = Vec::with_capacity(self.edges_count());
+        let mut values = Vec::with_capacity(self.edges_count());
+
+        offsets.push(0);
+        for neighbours in self.vertices.values() {
+            for ([_, to], edge) in neighbours {
+                columns.push(indices[to]);
+                values.push(weight(edge));
+            }
+            offsets.push(columns.len());
+        }
+
+        Csr {
+            vertices,
+            indices,
+            offsets,
+            columns,
+            values,
+        }
+    }
+
+    /// Creates graph from square adjacency matrix and labels of the vertices,
+    /// `edge` returns label of the edge for the matrix cell or `None` if there is no edge
+    ///
+    /// Returns [`GraphOperationError::MatrixSizeMismatch`] if the matrix isn't square
+    /// or its size differs from the number of labels
+    ///
+    /// ```
+    /// use simple_graph::{Graph, GraphOperationError};
+    ///
+    /// let labels = ["Moscow".to_string(), "Vladimir".to_string()];
+    /// let matrix = [[0, 180], [0, 0]];
+    ///
+    /// let graph: Graph<String, u32> =
+    ///     Graph::from_adjacency_matrix(labels.clone(), &matrix, |&w| (w != 0).then_some(w)).unwrap();
+    /// let moscow = graph.get_vertex_id(&"Moscow".into());
+    /// let vladimir = graph.get_vertex_id(&"Vladimir".into());
+    /// assert_eq!(graph.get_edge_value(moscow, vladimir), Ok(&180));
+    /// assert_eq!(graph.edges_count(), 1);
+    ///
+    /// assert_eq!(
+    ///     Graph::<String, u32>::from_adjacency_matrix(labels, &[[0, 180]], |&w| Some(w)),
+    ///     Err(GraphOperationError::MatrixSizeMismatch)
+    /// );
+    /// ```
+    pub fn from_adjacency_matrix<T, R: AsRef<[T]>>(
+        vertices: impl IntoIterator<Item = V>,
+        matrix: &[R],
+        edge: impl Fn(&T) -> Option<E>,
+    ) -> Result<Self> {
+        let mut graph = Self::with_capacity(matrix.len());
+        let ids = graph.extend_vertices(vertices)?;
+        if ids.len() != matrix.len() || matrix.iter().any(|row| row.as_ref().len() != ids.len()) {
+            return Err(GraphOperationError::MatrixSizeMismatch);
+        }
+
+        for (&from, row) in ids.iter().zip(matrix) {
+            for (&to, value) in ids.iter().zip(row.as_ref()) {
+                if let Some(label) = edge(value) {
+                    graph.add_edge(from, to, label)?;
+                }
+            }
+        }
+        Ok(graph)
+    }
+}
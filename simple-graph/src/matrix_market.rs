@@ -0,0 +1,129 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::{
+    Graph, Label, NumberedGraph, ParseGraphError, numbered_edges, parse_index, parse_weight,
+};
+
+/// Formats [`Graph<usize, W>`] as sparse matrix in Matrix Market coordinate format
+///
+/// Entry `(i, j)` of the matrix is the label of the edge from vertex `i` to vertex `j`.
+/// Labels of the vertices are used as their numbers, so they must start from 1
+///
+/// ```
+/// use simple_graph::{Graph, MatrixMarket};
+///
+/// let s = concat!(
+///     "%%MatrixMarket matrix coordinate real general\n",
+///     "3 3 2\n",
+///     "1 2 0.5\n",
+///     "2 3 1.5\n",
+/// );
+/// let graph = Graph::<usize, String>::from_matrix_market(s).unwrap();
+/// assert_eq!(MatrixMarket(&graph).to_string(), s);
+/// ```
+pub struct MatrixMarket<'a, W: Label>(pub &'a Graph<usize, W>);
+
+impl<W: Label> Display for MatrixMarket<'_, W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (vertices, edges) = numbered_edges(self.0)?;
+        writeln!(f, "%%MatrixMarket matrix coordinate real general")?;
+        writeln!(f, "{vertices} {vertices} {}", edges.len())?;
+        for (from, to, weight) in edges {
+            writeln!(f, "{from} {to} {weight}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Label> Graph<usize, W> {
+    /// Parses [`Graph<usize, W>`] from [`&str`] in Matrix Market coordinate format
+    ///
+    /// Vertices are labeled by their numbers `1..=max(rows, columns)`, values of the entries become
+    /// labels of the edges or `W::default()` for `pattern` matrices.
+    /// Entries of `symmetric` matrices are added in both directions,
+    /// `skew-symmetric` and `hermitian` matrices are not supported.
+    /// Returns [`ParseGraphError::Syntax`] if the matrix has more than 2<sup>24</sup> rows or columns
+    ///
+    /// ```
+    /// use simple_graph::{Graph, ParseGraphError};
+    ///
+    /// let s = concat!(
+    ///     "%%MatrixMarket matrix coordinate integer symmetric\n",
+    ///     "% distances\n",
+    ///     "3 3 2\n",
+    ///     "2 1 180\n",
+    ///     "3 1 250\n",
+    /// );
+    /// let graph = Graph::<usize, u32>::from_matrix_market(s).unwrap();
+    /// assert_eq!(graph.edges_count(), 4);
+    /// assert_eq!(graph.get_edge_value(graph.get_vertex_id(&1), graph.get_vertex_id(&3)), Ok(&250));
+    ///
+    /// let huge = concat!(
+    ///     "%%MatrixMarket matrix coordinate integer general\n",
+    ///     "18446744073709551615 1 0\n",
+    /// );
+    /// let error = Graph::<usize, u32>::from_matrix_market(huge).unwrap_err();
+    /// assert_eq!(error, ParseGraphError::Syntax("too many vertices", 2));
+    ///
+    /// let skew = "%%MatrixMarket matrix coordinate integer skew-symmetric\n1 1 0\n";
+    /// let error = Graph::<usize, i32>::from_matrix_market(skew).unwrap_err();
+    /// assert_eq!(error, ParseGraphError::Syntax("unsupported symmetry", 1));
+    /// ```
+    pub fn from_matrix_market(s: &str) -> Result<Self, ParseGraphError> {
+        let mut lines = (1_usize..).zip(s.lines());
+
+        let (_, banner) = lines
+            .next()
+            .ok_or(ParseGraphError::Syntax("expected header", 1))?;
+        let banner = banner.to_ascii_lowercase();
+        let banner = banner.split_whitespace().collect::<Vec<_>>();
+        let (field, symmetry) = match banner.as_slice() {
+            ["%%matrixmarket", "matrix", "coordinate", field, symmetry] => (*field, *symmetry),
+            _ => {
+                return Err(ParseGraphError::Syntax(
+                    "expected `%%MatrixMarket matrix coordinate` header",
+                    1,
+                ));
+            }
+        };
+        if field == "complex" {
+            return Err(ParseGraphError::Syntax(
+                "complex matrices are not supported",
+                1,
+            ));
+        }
+        let pattern = field == "pattern";
+        let symmetric = match symmetry {
+            "general" => false,
+            "symmetric" => true,
+            _ => return Err(ParseGraphError::Syntax("unsupported symmetry", 1)),
+        };
+
+        let mut lines = lines.filter(|(_, line)| {
+            let line = line.trim_start();
+            !line.is_empty() && !line.starts_with('%')
+        });
+
+        let (n, size) = lines
+            .next()
+            .ok_or(ParseGraphError::Syntax("expected size line", 1))?;
+        let mut it = size.split_whitespace();
+        let rows = parse_index(it.next().unwrap_or(""), n)?;
+        let columns = parse_index(it.next().unwrap_or(""), n)?;
+
+        let mut graph = NumberedGraph::new(rows.max(columns), n)?;
+        for (n, line) in lines {
+            let mut it = line.split_whitespace();
+            let from = parse_index(it.next().unwrap_or(""), n)?;
+            let to = parse_index(it.next().unwrap_or(""), n)?;
+            let value = if pattern { None } else { it.next() };
+
+            graph.add_edge(from, to, parse_weight(value, n)?, n)?;
+            if symmetric && from != to {
+                graph.add_edge(to, from, parse_weight(value, n)?, n)?;
+            }
+        }
+
+        Ok(graph.finish())
+    }
+}
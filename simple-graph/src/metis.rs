@@ -0,0 +1,132 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::{
+    Graph, Label, NumberedGraph, ParseGraphError, numbered_edges, parse_index, parse_weight,
+};
+
+/// Formats [`Graph<usize, W>`] in METIS graph format with edge weights (`fmt` is `001`)
+///
+/// METIS describes undirected graphs, so every edge must be present in both directions
+/// with the same weight, otherwise formatting fails with [`fmt::Error`].
+/// Labels of the vertices are used as their numbers, so they must start from 1
+///
+/// ```
+/// use simple_graph::{Graph, Metis};
+/// use std::fmt::Write;
+///
+/// let s = concat!(
+///     "3 2 001\n",
+///     "2 5\n",
+///     "1 5 3 7\n",
+///     "2 7\n",
+/// );
+/// let graph = Graph::<usize, u32>::from_metis(s).unwrap();
+/// assert_eq!(Metis(&graph).to_string(), s);
+///
+/// let mut directed = Graph::<usize, u32>::new();
+/// let [first, second] = [1, 2].map(|i| directed.add_vertex(i).unwrap());
+/// directed.add_edge(first, second, 5).unwrap();
+/// assert!(write!(String::new(), "{}", Metis(&directed)).is_err());
+/// ```
+pub struct Metis<'a, W: Label>(pub &'a Graph<usize, W>);
+
+impl<W: Label> Display for Metis<'_, W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (vertices, edges) = numbered_edges(self.0)?;
+        let mut adjacency = vec![Vec::new(); vertices];
+        for (from, to, weight) in &edges {
+            adjacency
+                .get_mut(from.wrapping_sub(1))
+                .ok_or(fmt::Error)?
+                .push((to, weight));
+        }
+
+        // every edge must have a reverse twin with the same weight, self-loops are their own twins
+        let mut forward = edges.clone();
+        let mut backward = edges
+            .iter()
+            .map(|&(from, to, weight)| (to, from, weight))
+            .collect::<Vec<_>>();
+        forward.sort_unstable();
+        backward.sort_unstable();
+        if forward != backward {
+            return Err(fmt::Error);
+        }
+        let loops = edges.iter().filter(|(from, to, _)| from == to).count();
+
+        writeln!(f, "{vertices} {} 001", (edges.len() - loops) / 2 + loops)?;
+        for neighbours in adjacency {
+            let line = neighbours
+                .iter()
+                .map(|(to, weight)| format!("{to} {weight}"))
+                .collect::<Vec<_>>();
+            writeln!(f, "{}", line.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Label> Graph<usize, W> {
+    /// Parses [`Graph<usize, W>`] from [`&str`] in METIS graph format
+    ///
+    /// Vertices are labeled by their numbers `1..=n`. Vertex sizes and weights are skipped,
+    /// edge weights become labels of the edges or `W::default()` if the graph is unweighted.
+    /// Every line describes all neighbours of the vertex, so undirected edges appear in both directions
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    ///
+    /// let s = concat!(
+    ///     "% triangle without weights\n",
+    ///     "3 3\n",
+    ///     "2 3\n",
+    ///     "1 3\n",
+    ///     "1 2\n",
+    /// );
+    /// let graph = Graph::<usize, u32>::from_metis(s).unwrap();
+    /// assert_eq!(graph.edges_count(), 6);
+    /// assert_eq!(graph.get_edge_value(graph.get_vertex_id(&3), graph.get_vertex_id(&1)), Ok(&0));
+    /// ```
+    pub fn from_metis(s: &str) -> Result<Self, ParseGraphError> {
+        let mut lines = (1_usize..)
+            .zip(s.lines())
+            .filter(|(_, line)| !line.trim_start().starts_with('%'));
+
+        let (n, header) = lines
+            .next()
+            .ok_or(ParseGraphError::Syntax("expected header", 1))?;
+        let mut it = header.split_whitespace();
+        let vertices = parse_index(it.next().unwrap_or(""), n)?;
+        let _edges = parse_index(it.next().unwrap_or(""), n)?;
+        let format = it.next().unwrap_or("0");
+        let constraints = it.next().map_or(Ok(1), |s| parse_index(s, n))?;
+
+        let flag = |position: usize| {
+            format
+                .len()
+                .checked_sub(position + 1)
+                .and_then(|i| format.as_bytes().get(i))
+                == Some(&b'1')
+        };
+        let (edge_weights, vertex_weights, vertex_sizes) = (flag(0), flag(1), flag(2));
+        let skip = usize::from(vertex_sizes) + if vertex_weights { constraints } else { 0 };
+
+        let mut graph = NumberedGraph::new(vertices, n)?;
+        for from in 1..=vertices {
+            let (n, line) = lines
+                .next()
+                .ok_or(ParseGraphError::Syntax("not enough vertex lines", n))?;
+            let mut it = line.split_whitespace().skip(skip);
+            while let Some(to) = it.next() {
+                let to = parse_index(to, n)?;
+                let weight = match edge_weights {
+                    true => parse_weight(Some(it.next().unwrap_or("")), n)?,
+                    false => parse_weight(None, n)?,
+                };
+                graph.add_edge(from, to, weight, n)?;
+            }
+        }
+
+        Ok(graph.finish())
+    }
+}
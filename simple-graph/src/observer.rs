@@ -0,0 +1,107 @@
+use std::fmt;
+
+use super::{Label, VertexId};
+
+/// Change of the graph which is sent to observers, see [`crate::Graph::subscribe`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum GraphEvent<V: Label, E: Label> {
+    /// new vertex is added
+    VertexAdded { vertex_id: VertexId, vertex: V },
+    /// the vertex is removed, `edges` are its outgoing and incoming edges `([from, to], label)`
+    /// which are removed with it
+    VertexRemoved {
+        vertex_id: VertexId,
+        vertex: V,
+        edges: Vec<([VertexId; 2], E)>,
+    },
+    /// new edge is added, adding the existing edge isn't reported
+    EdgeAdded {
+        from: VertexId,
+        to: VertexId,
+        edge: E,
+    },
+    /// the edge is removed
+    EdgeRemoved {
+        from: VertexId,
+        to: VertexId,
+        edge: E,
+    },
+    /// label of the edge is replaced, the edge keeps its position
+    EdgeUpdated {
+        from: VertexId,
+        to: VertexId,
+        old: E,
+        new: E,
+    },
+}
+
+/// Identifies the observer added by [`crate::Graph::subscribe`]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct ObserverId(usize);
+
+type Observer<V, E> = Box<dyn FnMut(&GraphEvent<V, E>) + Send + Sync>;
+
+/// Observers of the graph, they aren't cloned with the graph and don't affect its equality
+pub(crate) struct Observers<V: Label, E: Label> {
+    observers: Vec<(ObserverId, Observer<V, E>)>,
+    next_id: usize,
+}
+
+impl<V: Label, E: Label> Observers<V, E> {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.observers.is_empty()
+    }
+
+    pub(crate) fn add(&mut self, observer: Observer<V, E>) -> ObserverId {
+        let id = ObserverId(self.next_id);
+        self.next_id += 1;
+        self.observers.push((id, observer));
+        id
+    }
+
+    pub(crate) fn remove(&mut self, id: ObserverId) -> bool {
+        let count = self.observers.len();
+        self.observers.retain(|(observer_id, _)| *observer_id != id);
+        self.observers.len() != count
+    }
+
+    /// Sends the event to all observers, the event is created only if there are any
+    pub(crate) fn notify<F: FnOnce() -> GraphEvent<V, E>>(&mut self, event: F) {
+        if self.is_empty() {
+            return;
+        }
+        let event = event();
+        for (_, observer) in &mut self.observers {
+            observer(&event);
+        }
+    }
+}
+
+impl<V: Label, E: Label> Default for Observers<V, E> {
+    fn default() -> Self {
+        Self {
+            observers: Vec::new(),
+            next_id: 0,
+        }
+    }
+}
+
+impl<V: Label, E: Label> Clone for Observers<V, E> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl<V: Label, E: Label> PartialEq for Observers<V, E> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<V: Label, E: Label> Eq for Observers<V, E> {}
+
+impl<V: Label, E: Label> fmt::Debug for Observers<V, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} observers", self.observers.len())
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+
+use super::{
+    Graph, Label, MAX_NUMBERED_VERTICES, ParseGraphError, TgfEvent, TgfGraphBuilder, TgfLayout,
+    TgfOptions, TgfOrder, parse_index, parse_label,
+};
+
+/// Formats [`Graph<V, E>`] in [Pajek NET](http://mrvar.fdv.uni-lj.si/pajek/) format
+///
+/// All edges are written in the `*Arcs` section because [`Graph<V, E>`] is directed,
+/// label of the edge is written as its weight. Labels with whitespace or quotes are written
+/// in double quotes, `"` and `\` inside them are escaped with `\`
+///
+/// ```
+/// use simple_graph::{Graph, Pajek};
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, u32> =
+///     Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+///
+/// let expected = concat!(
+///     "*Vertices 5\n",
+///     "1 \"Moscow\"\n",
+///     "2 \"Vladimir\"\n",
+///     "3 \"Yaroslavl\"\n",
+///     "4 \"Novgorod\"\n",
+///     "5 \"Vologda\"\n",
+///     "*Arcs\n",
+///     "1 2 180\n",
+///     "1 3 250\n",
+///     "2 4 225\n",
+///     "3 5 175\n",
+/// );
+/// assert_eq!(Pajek(&graph).to_string(), expected);
+/// assert_eq!(Graph::from_pajek(expected).unwrap(), graph);
+///
+/// // quotes and backslashes in labels are escaped
+/// let mut quotes = Graph::<String, String>::new();
+/// let from = quotes.add_vertex(r#"say "hi" now"#.into()).unwrap();
+/// let to = quotes.add_vertex(r"C:\path".into()).unwrap();
+/// quotes.add_edge(from, to, r#"a"b"#.into()).unwrap();
+///
+/// let s = Pajek(&quotes).to_string();
+/// assert_eq!(s.lines().nth(1), Some(r#"1 "say \"hi\" now""#));
+/// assert_eq!(s.lines().last(), Some(r#"1 2 "a\"b""#));
+/// assert_eq!(Graph::from_pajek(&s).unwrap(), quotes);
+/// ```
+pub struct Pajek<'a, V: Label, E: Label>(pub &'a Graph<V, E>);
+
+impl<V: Label, E: Label> Display for Pajek<'_, V, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let layout = TgfLayout::new(self.0, None, TgfOrder::Insertion).map_err(|_| fmt::Error)?;
+
+        writeln!(f, "*Vertices {}", layout.vertices.len())?;
+        for (index, vertex) in layout.vertices {
+            writeln!(f, "{index} {}", quote(&vertex.to_string()))?;
+        }
+        writeln!(f, "*Arcs")?;
+        for (from, to, edge) in layout.edges {
+            let edge = edge.to_string();
+            match edge.is_empty() {
+                true => writeln!(f, "{from} {to}")?,
+                false if edge.contains(|c: char| c.is_whitespace() || c == '"' || c == '\\') => {
+                    writeln!(f, "{from} {to} {}", quote(&edge))?
+                }
+                false => writeln!(f, "{from} {to} {edge}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Eq, PartialEq)]
+enum Section {
+    Header,
+    Vertices,
+    Arcs,
+    Edges,
+}
+
+impl<V: Label, E: Label> Graph<V, E> {
+    /// Parses [`Graph<V, E>`] from [`&str`] in Pajek NET format
+    ///
+    /// Vertices from `*Vertices` section which are not listed get their index as label,
+    /// the section can't declare more than 2<sup>24</sup> vertices.
+    /// Edges from `*Arcs` section are directed, edges from `*Edges` section are added in both directions.
+    /// Coordinates and other vertex attributes are ignored, lines starting with `%` are comments
+    ///
+    /// ```
+    /// use simple_graph::{Graph, ParseGraphError};
+    ///
+    /// let s = concat!(
+    ///     "% distances between cities\n",
+    ///     "*Vertices 3\n",
+    ///     "1 \"Moscow\" 0.1 0.5\n",
+    ///     "2 \"Vladimir\" 0.3 0.4\n",
+    ///     "*Arcs\n",
+    ///     "1 2 180\n",
+    ///     "*Edges\n",
+    ///     "1 3 250\n",
+    /// );
+    /// let graph = Graph::<String, u32>::from_pajek(s).unwrap();
+    ///
+    /// let moscow = graph.get_vertex_id(&"Moscow".into());
+    /// let vladimir = graph.get_vertex_id(&"Vladimir".into());
+    /// let third = graph.get_vertex_id(&"3".into());
+    ///
+    /// assert_eq!(graph.get_edge_value(moscow, vladimir), Ok(&180));
+    /// assert!(graph.get_edge_value(vladimir, moscow).is_err());
+    /// assert_eq!(graph.get_edge_value(moscow, third), Ok(&250));
+    /// assert_eq!(graph.get_edge_value(third, moscow), Ok(&250));
+    ///
+    /// let vertices_only = Graph::<String, u32>::from_pajek("*Vertices 3\n1 \"a\"\n").unwrap();
+    /// assert_eq!(vertices_only.vertices_count(), 3);
+    ///
+    /// let huge = Graph::<String, u32>::from_pajek("*Vertices 18446744073709551615\n");
+    /// assert_eq!(huge, Err(ParseGraphError::Syntax("too many vertices", 1)));
+    /// ```
+    pub fn from_pajek(s: &str) -> Result<Self, ParseGraphError> {
+        let mut builder = TgfGraphBuilder::new(&TgfOptions::default());
+        let mut section = Section::Header;
+        let mut count = 0;
+        let mut defined = HashSet::new();
+        let mut last = 0;
+
+        for (n, line) in (1_usize..).zip(s.lines()) {
+            last = n;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('%') {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('*') {
+                let mut it = header.split_whitespace();
+                let name = it.next().unwrap_or("").to_ascii_lowercase();
+                let next = match name.as_str() {
+                    "network" => continue,
+                    "vertices" => {
+                        count = parse_index(it.next().unwrap_or(""), n)?;
+                        if count > MAX_NUMBERED_VERTICES {
+                            return Err(ParseGraphError::Syntax("too many vertices", n));
+                        }
+                        Section::Vertices
+                    }
+                    "arcs" => Section::Arcs,
+                    "edges" => Section::Edges,
+                    _ => return Err(ParseGraphError::Syntax("unsupported section", n)),
+                };
+                if section == Section::Vertices {
+                    add_missing_vertices(&mut builder, count, &defined, n)?;
+                }
+                section = next;
+                continue;
+            }
+
+            match section {
+                Section::Header => {
+                    return Err(ParseGraphError::Syntax("expected `*Vertices` section", n));
+                }
+                Section::Vertices => {
+                    let (index, rest) =
+                        next_field(line).ok_or(ParseGraphError::VertexDefinition(n))?;
+                    let index = parse_index(&index, n)?;
+                    let (label, _) = next_field(rest).unwrap_or_else(|| (index.to_string(), ""));
+                    builder.push(TgfEvent::Vertex(index, parse_label(&label, n)?), n)?;
+                    defined.insert(index);
+                }
+                Section::Arcs | Section::Edges => {
+                    let (from, rest) =
+                        next_field(line).ok_or(ParseGraphError::EdgeDefinition(n))?;
+                    let (to, rest) = next_field(rest).ok_or(ParseGraphError::EdgeDefinition(n))?;
+                    let from = parse_index(&from, n)?;
+                    let to = parse_index(&to, n)?;
+                    let (weight, _) = next_field(rest).unwrap_or_default();
+
+                    builder.push(TgfEvent::Edge(from, to, parse_label(&weight, n)?), n)?;
+                    if section == Section::Edges && from != to {
+                        builder.push(TgfEvent::Edge(to, from, parse_label(&weight, n)?), n)?;
+                    }
+                }
+            }
+        }
+        if section == Section::Vertices {
+            add_missing_vertices(&mut builder, count, &defined, last)?;
+        }
+
+        Ok(builder.finish().0)
+    }
+}
+
+/// Adds vertices `1..=count` which are not `defined` with their index as label
+fn add_missing_vertices<V: Label, E: Label>(
+    builder: &mut TgfGraphBuilder<V, E>,
+    count: usize,
+    defined: &HashSet<usize>,
+    n: usize,
+) -> Result<(), ParseGraphError> {
+    for index in (1..=count).filter(|index| !defined.contains(index)) {
+        let label = parse_label(&index.to_string(), n)?;
+        builder.push(TgfEvent::Vertex(index, label), n)?;
+    }
+    Ok(())
+}
+
+/// Wraps the label in double quotes, `"` and `\` inside it are escaped with `\`
+fn quote(label: &str) -> String {
+    let mut quoted = String::with_capacity(label.len() + 2);
+    quoted.push('"');
+    for c in label.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Splits the first field which is either a word or a string in double quotes,
+/// `\"` and `\\` inside quotes are unescaped
+fn next_field(s: &str) -> Option<(String, &str)> {
+    let s = s.trim_start();
+    if let Some(rest) = s.strip_prefix('"') {
+        let mut field = String::new();
+        let mut chars = rest.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => return Some((field, &rest[i + 1..])),
+                '\\' => field.push(chars.next().map_or(c, |(_, c)| c)),
+                _ => field.push(c),
+            }
+        }
+        return None;
+    }
+    let end = s.find(char::is_whitespace).unwrap_or(s.len());
+    match end {
+        0 => None,
+        _ => Some((s[..end].into(), &s[end..])),
+    }
+}
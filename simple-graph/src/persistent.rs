@@ -0,0 +1,531 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use linked_hash_map::LinkedHashMap;
+use linked_hash_set::LinkedHashSet;
+
+use super::{
+    Build, EdgeWeights, Graph, GraphBase, GraphOperationError, Label, Neighbors, Result,
+    VertexCount, VertexId, Vertices,
+};
+
+/// Count of the key bits which select a child on every level of the trie
+const BITS: u32 = 5;
+const MASK: u64 = (1 << BITS) - 1;
+
+/// Node of the hash array mapped trie, unchanged nodes are shared between versions
+#[derive(Debug, Clone)]
+enum Node<T> {
+    Leaf(u64, T),
+    /// bitmap of the present children and children in order of their bits
+    Branch(u32, Vec<Arc<Node<T>>>),
+}
+
+/// Returns bit of the child which contains the key and its index among present children
+fn slot(bitmap: u32, key: u64, shift: u32) -> (u32, usize) {
+    let bit = 1 << ((key >> shift) & MASK);
+    (bit, (bitmap & (bit - 1)).count_ones() as usize)
+}
+
+/// Persistent map from hashes, every change copies only nodes on the path to the key
+#[derive(Debug, Clone)]
+struct Trie<T> {
+    root: Option<Arc<Node<T>>>,
+    len: usize,
+}
+
+impl<T> Default for Trie<T> {
+    fn default() -> Self {
+        Self { root: None, len: 0 }
+    }
+}
+
+impl<T: Clone> Trie<T> {
+    fn get(&self, key: u64) -> Option<&T> {
+        let mut node = self.root.as_deref()?;
+        let mut shift = 0;
+        loop {
+            match node {
+                Node::Leaf(k, value) => return (*k == key).then_some(value),
+                Node::Branch(bitmap, children) => {
+                    let (bit, index) = slot(*bitmap, key, shift);
+                    if bitmap & bit == 0 {
+                        return None;
+                    }
+                    node = &children[index];
+                    shift += BITS;
+                }
+            }
+        }
+    }
+
+    fn get_mut(&mut self, key: u64) -> Option<&mut T> {
+        fn get_mut<T: Clone>(node: &mut Arc<Node<T>>, key: u64, shift: u32) -> Option<&mut T> {
+            match Arc::make_mut(node) {
+                Node::Leaf(k, value) => (*k == key).then_some(value),
+                Node::Branch(bitmap, children) => {
+                    let (bit, index) = slot(*bitmap, key, shift);
+                    if *bitmap & bit == 0 {
+                        return None;
+                    }
+                    get_mut(&mut children[index], key, shift + BITS)
+                }
+            }
+        }
+
+        // nodes are copied only if the key exists
+        self.get(key)?;
+        get_mut(self.root.as_mut()?, key, 0)
+    }
+
+    fn insert(&mut self, key: u64, value: T) {
+        fn insert<T: Clone>(node: &mut Arc<Node<T>>, key: u64, value: T, shift: u32) -> bool {
+            // leaf with another key is moved one level down
+            if let Node::Leaf(existing, _) = node.as_ref()
+                && *existing != key
+            {
+                let (bit, _) = slot(0, *existing, shift);
+                *node = Arc::new(Node::Branch(bit, vec![node.clone()]));
+            }
+            match Arc::make_mut(node) {
+                Node::Leaf(_, existing) => {
+                    *existing = value;
+                    false
+                }
+                Node::Branch(bitmap, children) => {
+                    let (bit, index) = slot(*bitmap, key, shift);
+                    if *bitmap & bit == 0 {
+                        *bitmap |= bit;
+                        children.insert(index, Arc::new(Node::Leaf(key, value)));
+                        return true;
+                    }
+                    insert(&mut children[index], key, value, shift + BITS)
+                }
+            }
+        }
+
+        let inserted = match &mut self.root {
+            Some(root) => insert(root, key, value, 0),
+            None => {
+                self.root = Some(Arc::new(Node::Leaf(key, value)));
+                true
+            }
+        };
+        if inserted {
+            self.len += 1;
+        }
+    }
+
+    fn remove(&mut self, key: u64) -> Option<T> {
+        /// Returns the node without the key, `None` if it becomes empty
+        fn remove<T>(node: &Arc<Node<T>>, key: u64, shift: u32) -> Option<Arc<Node<T>>> {
+            let Node::Branch(bitmap, children) = node.as_ref() else {
+                return None;
+            };
+            let (bit, index) = slot(*bitmap, key, shift);
+            let mut bitmap = *bitmap;
+            let mut children = children.clone();
+            match remove(&children[index], key, shift + BITS) {
+                Some(child) => children[index] = child,
+                None => {
+                    bitmap &= !bit;
+                    children.remove(index);
+                }
+            }
+            match children.as_slice() {
+                [] => None,
+                [child] if matches!(child.as_ref(), Node::Leaf(..)) => Some(child.clone()),
+                _ => Some(Arc::new(Node::Branch(bitmap, children))),
+            }
+        }
+
+        let value = self.get(key)?.clone();
+        self.root = self.root.as_ref().and_then(|root| remove(root, key, 0));
+        self.len -= 1;
+        Some(value)
+    }
+
+    fn values(&self) -> Vec<&T> {
+        fn collect<'a, T>(node: &'a Node<T>, values: &mut Vec<&'a T>) {
+            match node {
+                Node::Leaf(_, value) => values.push(value),
+                Node::Branch(_, children) => {
+                    for child in children {
+                        collect(child, values);
+                    }
+                }
+            }
+        }
+
+        let mut values = Vec::with_capacity(self.len);
+        if let Some(root) = &self.root {
+            collect(root, &mut values);
+        }
+        values
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry<V, E> {
+    /// position of the vertex in insertion order
+    order: u64,
+    vertex: V,
+    edges: Arc<Vec<([VertexId; 2], E)>>,
+}
+
+/// Directed graph with the same API as [`Graph`] which shares unchanged parts between its
+/// versions, so [`Clone::clone`] takes `O(1)` time
+///
+/// Vertices are stored in hash array mapped trie, so changes copy `O(log V)` nodes
+/// and the edges of the changed vertex. The graph can be converted from and into [`Graph`],
+/// insertion order of vertices and edges is kept
+///
+/// ```
+/// use simple_graph::{Graph, PersistentGraph};
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+/// let first = PersistentGraph::from(&graph);
+///
+/// let mut second = first.clone();
+/// let moscow = second.get_vertex_id(&"Moscow".into());
+/// let kazan = second.add_vertex("Kazan".into()).unwrap();
+/// second.add_edge(moscow, kazan, 800).unwrap();
+///
+/// assert_eq!(first.vertices_count(), 5);
+/// assert_eq!(second.vertices_count(), 6);
+/// assert_eq!(Graph::from(&first), graph);
+/// assert_eq!(second.get_edge_value(moscow, kazan), Ok(&800));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PersistentGraph<V: Label, E: Label> {
+    vertices: Trie<Entry<V, E>>,
+    edges_count: usize,
+    next_order: u64,
+}
+
+impl<V: Label, E: Label> PersistentGraph<V, E> {
+    /// Creates new graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new version of the graph with applied changes, this version stays unchanged
+    ///
+    /// ```
+    /// use simple_graph::{GraphOperationError, PersistentGraph};
+    ///
+    /// let empty = PersistentGraph::<String, u32>::new();
+    /// let moscow = empty.update(|graph| graph.add_vertex("Moscow".into())).unwrap();
+    ///
+    /// assert_eq!(empty.vertices_count(), 0);
+    /// assert_eq!(moscow.vertices_count(), 1);
+    /// assert_eq!(
+    ///     moscow.update(|graph| graph.add_vertex("Moscow".into())).unwrap_err(),
+    ///     GraphOperationError::VertexAlreadyExists
+    /// );
+    /// ```
+    pub fn update<T, F>(&self, changes: F) -> Result<Self>
+    where
+        F: FnOnce(&mut Self) -> Result<T>,
+    {
+        let mut graph = self.clone();
+        changes(&mut graph)?;
+        Ok(graph)
+    }
+
+    /// Gets [`VertexId`] by it's value, it's the same as [`Graph::get_vertex_id`]
+    pub fn get_vertex_id(&self, vertex: &V) -> VertexId {
+        VertexId::new(vertex)
+    }
+
+    /// Trying to add vertex to the graph, returns [`GraphOperationError::VertexAlreadyExists`]
+    /// if can't do this
+    pub fn add_vertex(&mut self, vertex: V) -> Result<VertexId> {
+        let vertex_id = self.get_vertex_id(&vertex);
+        if self.vertices.get(vertex_id.0).is_some() {
+            return Err(GraphOperationError::VertexAlreadyExists);
+        }
+        let entry = Entry {
+            order: self.next_order,
+            vertex,
+            edges: Arc::default(),
+        };
+        self.vertices.insert(vertex_id.0, entry);
+        self.next_order += 1;
+        Ok(vertex_id)
+    }
+
+    /// Trying to get vertex by id, returns [`GraphOperationError::VertexDoesNotExist`]
+    /// if can't do this
+    pub fn get_vertex(&self, vertex_id: VertexId) -> Result<&V> {
+        self.entry(vertex_id).map(|entry| &entry.vertex)
+    }
+
+    /// Trying to remove vertex with its outgoing and incoming edges,
+    /// returns [`GraphOperationError::VertexDoesNotExist`] if can't do this
+    ///
+    /// Incoming edges aren't indexed, so it takes `O(V)` time
+    ///
+    /// ```
+    /// use simple_graph::{Graph, PersistentGraph};
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let mut persistent = PersistentGraph::from(&graph);
+    ///
+    /// persistent.remove_vertex(persistent.get_vertex_id(&"Vladimir".into())).unwrap();
+    /// assert_eq!(persistent.vertices_count(), 4);
+    /// assert_eq!(persistent.edges_count(), 2);
+    /// ```
+    pub fn remove_vertex(&mut self, vertex_id: VertexId) -> Result<()> {
+        self.entry(vertex_id)?;
+        let sources = self
+            .vertices
+            .values()
+            .into_iter()
+            .flat_map(|entry| entry.edges.iter())
+            .filter(|([from, to], _)| *to == vertex_id && *from != vertex_id)
+            .map(|([from, _], _)| *from)
+            .collect::<Vec<_>>();
+
+        for from in sources {
+            if let Some(entry) = self.vertices.get_mut(from.0) {
+                let edges = Arc::make_mut(&mut entry.edges);
+                let count = edges.len();
+                edges.retain(|([_, to], _)| *to != vertex_id);
+                self.edges_count -= count - edges.len();
+            }
+        }
+        if let Some(entry) = self.vertices.remove(vertex_id.0) {
+            self.edges_count -= entry.edges.len();
+        }
+        Ok(())
+    }
+
+    /// Trying to add edge between two vertices, returns [`GraphOperationError::VertexDoesNotExist`]
+    /// if can't do this
+    ///
+    /// Like in [`Graph::add_edge`] the same edge is only moved after other edges of the vertex
+    pub fn add_edge(&mut self, from: VertexId, to: VertexId, edge: E) -> Result<()> {
+        self.entry(to)?;
+        let entry = self
+            .vertices
+            .get_mut(from.0)
+            .ok_or(GraphOperationError::VertexDoesNotExist)?;
+        let edges = Arc::make_mut(&mut entry.edges);
+        let edge = ([from, to], edge);
+        match edges.iter().position(|e| *e == edge) {
+            Some(position) => {
+                edges.remove(position);
+            }
+            None => self.edges_count += 1,
+        }
+        edges.push(edge);
+        Ok(())
+    }
+
+    /// Trying to get edge **value** between two vertices,
+    /// returns [`GraphOperationError::EdgeDoesNotExist`] if can't do this
+    pub fn get_edge_value(&self, from: VertexId, to: VertexId) -> Result<&E> {
+        self.vertices
+            .get(from.0)
+            .and_then(|entry| entry.edges.iter().find(|([_, t], _)| *t == to))
+            .map(|(_, edge)| edge)
+            .ok_or(GraphOperationError::EdgeDoesNotExist)
+    }
+
+    /// Trying to remove edge between two vertices, returns [`GraphOperationError::EdgeDoesNotExist`]
+    /// if can't do this
+    ///
+    /// ```
+    /// use simple_graph::{Graph, GraphOperationError, PersistentGraph};
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let before = PersistentGraph::from(&graph);
+    ///
+    /// let moscow = graph.get_vertex_id(&"Moscow".into());
+    /// let vladimir = graph.get_vertex_id(&"Vladimir".into());
+    /// let after = before.update(|graph| graph.remove_edge(moscow, vladimir)).unwrap();
+    ///
+    /// assert_eq!(before.get_edge_value(moscow, vladimir), Ok(&180));
+    /// assert_eq!(after.get_edge_value(moscow, vladimir), Err(GraphOperationError::EdgeDoesNotExist));
+    /// ```
+    pub fn remove_edge(&mut self, from: VertexId, to: VertexId) -> Result<()> {
+        self.get_edge_value(from, to)?;
+        if let Some(entry) = self.vertices.get_mut(from.0) {
+            let edges = Arc::make_mut(&mut entry.edges);
+            if let Some(position) = edges.iter().position(|([_, t], _)| *t == to) {
+                edges.remove(position);
+                self.edges_count -= 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns count of vertices in the graph
+    pub fn vertices_count(&self) -> usize {
+        self.vertices.len
+    }
+
+    /// Returns count of edges in the graph
+    pub fn edges_count(&self) -> usize {
+        self.edges_count
+    }
+
+    /// Returns the vertex with adjacent vertices and labels of the edges to them,
+    /// returns [`GraphOperationError::VertexDoesNotExist`] if the vertex doesn't exist
+    ///
+    /// ```
+    /// use simple_graph::{Graph, PersistentGraph};
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let persistent = PersistentGraph::from(&graph);
+    ///
+    /// let moscow = persistent.get_vertex_id(&"Moscow".into());
+    /// assert_eq!(persistent.get_vertex_info(moscow), graph.get_vertex_info(moscow));
+    /// ```
+    pub fn get_vertex_info(&self, vertex_id: VertexId) -> Result<(&V, Vec<(&V, &E)>)> {
+        let entry = self.entry(vertex_id)?;
+        let mut adjacent_vertices = Vec::with_capacity(entry.edges.len());
+        for ([_, to], edge) in entry.edges.iter() {
+            adjacent_vertices.push((self.get_vertex(*to)?, edge));
+        }
+        Ok((&entry.vertex, adjacent_vertices))
+    }
+
+    fn entry(&self, vertex_id: VertexId) -> Result<&Entry<V, E>> {
+        self.vertices
+            .get(vertex_id.0)
+            .ok_or(GraphOperationError::VertexDoesNotExist)
+    }
+}
+
+impl<V: Label, E: Label> From<&Graph<V, E>> for PersistentGraph<V, E> {
+    /// Copies the graph, it takes `O(V + E)` time
+    fn from(graph: &Graph<V, E>) -> Self {
+        let mut persistent = Self::new();
+        for (order, (vertex_id, neighbours)) in (0..).zip(graph.vertices.iter()) {
+            let Some(vertex) = graph.vertices_data.get(vertex_id) else {
+                continue;
+            };
+            let entry = Entry {
+                order,
+                vertex: vertex.clone(),
+                edges: Arc::new(neighbours.iter().cloned().collect()),
+            };
+            persistent.vertices.insert(vertex_id.0, entry);
+            persistent.edges_count += neighbours.len();
+            persistent.next_order = order + 1;
+        }
+        persistent
+    }
+}
+
+impl<V: Label, E: Label> From<&PersistentGraph<V, E>> for Graph<V, E> {
+    /// Copies the graph, vertices are sorted in insertion order, so it takes `O(V log V + E)` time
+    fn from(persistent: &PersistentGraph<V, E>) -> Self {
+        let mut entries = persistent.vertices.values();
+        entries.sort_unstable_by_key(|entry| entry.order);
+
+        let mut vertices = LinkedHashMap::with_capacity(entries.len());
+        let mut vertices_data = HashMap::with_capacity(entries.len());
+        for entry in entries {
+            let vertex_id = VertexId::new(&entry.vertex);
+            let edges = entry.edges.iter().cloned().collect::<LinkedHashSet<_>>();
+            vertices.insert(vertex_id, edges);
+            vertices_data.insert(vertex_id, entry.vertex.clone());
+        }
+        Graph {
+            vertices,
+            vertices_data,
+            observers: Default::default(),
+        }
+    }
+}
+
+impl<V: Label, E: Label> From<Graph<V, E>> for PersistentGraph<V, E> {
+    fn from(graph: Graph<V, E>) -> Self {
+        Self::from(&graph)
+    }
+}
+
+impl<V: Label, E: Label> From<PersistentGraph<V, E>> for Graph<V, E> {
+    fn from(persistent: PersistentGraph<V, E>) -> Self {
+        Self::from(&persistent)
+    }
+}
+
+impl<V: Label, E: Label> PartialEq for PersistentGraph<V, E> {
+    /// Graphs are equal if they have the same vertices with the same edges,
+    /// insertion order isn't compared
+    fn eq(&self, other: &Self) -> bool {
+        self.vertices_count() == other.vertices_count()
+            && self.edges_count == other.edges_count
+            && self.vertices.values().into_iter().all(|entry| {
+                other
+                    .vertices
+                    .get(VertexId::new(&entry.vertex).0)
+                    .is_some_and(|e| {
+                        e.vertex == entry.vertex
+                            && (Arc::ptr_eq(&e.edges, &entry.edges)
+                                || e.edges.iter().collect::<LinkedHashSet<_>>()
+                                    == entry.edges.iter().collect())
+                    })
+            })
+    }
+}
+
+impl<V: Label, E: Label> Eq for PersistentGraph<V, E> {}
+
+impl<V: Label, E: Label> GraphBase for PersistentGraph<V, E> {
+    type VertexId = VertexId;
+    type Vertex = V;
+    type Edge = E;
+
+    fn vertex(&self, vertex_id: VertexId) -> Result<&V> {
+        self.get_vertex(vertex_id)
+    }
+}
+
+impl<V: Label, E: Label> VertexCount for PersistentGraph<V, E> {
+    fn vertices_count(&self) -> usize {
+        PersistentGraph::vertices_count(self)
+    }
+}
+
+impl<V: Label, E: Label> Vertices for PersistentGraph<V, E> {
+    /// Vertices are sorted in insertion order, so it takes `O(V log V)` time
+    fn vertex_ids(&self) -> impl Iterator<Item = VertexId> {
+        let mut entries = self.vertices.values();
+        entries.sort_unstable_by_key(|entry| entry.order);
+        entries
+            .into_iter()
+            .map(|entry| VertexId::new(&entry.vertex))
+    }
+}
+
+impl<V: Label, E: Label> Neighbors for PersistentGraph<V, E> {
+    fn neighbors(&self, vertex_id: VertexId) -> Result<impl Iterator<Item = (VertexId, &E)>> {
+        let entry = self.entry(vertex_id)?;
+        Ok(entry.edges.iter().map(|([_, to], edge)| (*to, edge)))
+    }
+}
+
+impl<V: Label, E: Label> EdgeWeights for PersistentGraph<V, E> {
+    fn edge_weight(&self, from: VertexId, to: VertexId) -> Result<&E> {
+        self.get_edge_value(from, to)
+    }
+}
+
+impl<V: Label, E: Label> Build for PersistentGraph<V, E> {
+    fn add_vertex(&mut self, vertex: V) -> Result<VertexId> {
+        PersistentGraph::add_vertex(self, vertex)
+    }
+
+    fn add_edge(&mut self, from: VertexId, to: VertexId, edge: E) -> Result<()> {
+        PersistentGraph::add_edge(self, from, to, edge)
+    }
+}
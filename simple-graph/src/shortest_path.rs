@@ -0,0 +1,163 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::ops::Add;
+
+use super::{Graph, GraphOperationError, Label, Neighbors, Result, VertexId};
+
+/// Entry of the priority queue in Dijkstra's algorithm, the smallest distance goes first
+struct Candidate<W, I> {
+    distance: W,
+    vertex: I,
+}
+
+impl<W: PartialOrd, I> Ord for Candidate<W, I> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<W: PartialOrd, I> PartialOrd for Candidate<W, I> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: PartialOrd, I> PartialEq for Candidate<W, I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: PartialOrd, I> Eq for Candidate<W, I> {}
+
+/// Finds the shortest path between two vertices of any [`Neighbors`] storage using
+/// Dijkstra's algorithm, `weight` maps label of every edge into its non-negative length
+///
+/// Returns total length and ids of the vertices on the path including `from` and `to`,
+/// or `None` if `to` isn't reachable. Returns [`GraphOperationError::VertexDoesNotExist`]
+/// if any of the vertices doesn't exist and [`GraphOperationError::InvalidEdgeLength`]
+/// if length of a visited edge is negative or NaN
+///
+/// ```
+/// use simple_graph::{Graph, GraphOperationError, PersistentGraph, shortest_path};
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+/// let persistent = PersistentGraph::from(&graph);
+/// let id = |label: &str| graph.get_vertex_id(&label.into());
+///
+/// let (distance, path) = shortest_path(&persistent, id("Moscow"), id("Novgorod"), |&km| km)
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!(distance, 405);
+/// assert_eq!(path, [id("Moscow"), id("Vladimir"), id("Novgorod")]);
+///
+/// let error = shortest_path(&persistent, id("Moscow"), id("Novgorod"), |&km| -(km as i64));
+/// assert_eq!(error, Err(GraphOperationError::InvalidEdgeLength));
+/// let error = shortest_path(&persistent, id("Moscow"), id("Novgorod"), |_| f64::NAN);
+/// assert_eq!(error, Err(GraphOperationError::InvalidEdgeLength));
+/// ```
+pub fn shortest_path<G, W, F>(
+    graph: &G,
+    from: G::VertexId,
+    to: G::VertexId,
+    weight: F,
+) -> Result<Option<(W, Vec<G::VertexId>)>>
+where
+    G: Neighbors,
+    W: Copy + Default + PartialOrd + Add<Output = W>,
+    F: Fn(&G::Edge) -> W,
+{
+    graph.vertex(from)?;
+    graph.vertex(to)?;
+
+    let mut distances = HashMap::from([(from, W::default())]);
+    let mut previous = HashMap::new();
+    let mut queue = BinaryHeap::from([Candidate {
+        distance: W::default(),
+        vertex: from,
+    }]);
+
+    while let Some(Candidate { distance, vertex }) = queue.pop() {
+        if vertex == to {
+            let mut path = vec![to];
+            while let Some(&vertex) = previous.get(path.last().unwrap_or(&to)) {
+                path.push(vertex);
+            }
+            path.reverse();
+            return Ok(Some((distance, path)));
+        }
+        // queue may contain outdated entries for the same vertex
+        if distances.get(&vertex).is_some_and(|&best| distance > best) {
+            continue;
+        }
+
+        for (next, edge) in graph.neighbors(vertex)? {
+            let length = weight(edge);
+            if length
+                .partial_cmp(&W::default())
+                .is_none_or(|ordering| ordering == Ordering::Less)
+            {
+                return Err(GraphOperationError::InvalidEdgeLength);
+            }
+            let candidate = distance + length;
+            if distances.get(&next).is_none_or(|&best| candidate < best) {
+                distances.insert(next, candidate);
+                previous.insert(next, vertex);
+                queue.push(Candidate {
+                    distance: candidate,
+                    vertex: next,
+                });
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+impl<V: Label, E: Label> Graph<V, E> {
+    /// Finds the shortest path between two vertices using Dijkstra's algorithm,
+    /// `weight` maps label of every edge into its non-negative length
+    ///
+    /// Returns total length and ids of the vertices on the path including `from` and `to`,
+    /// or `None` if `to` isn't reachable. Returns [`GraphOperationError::VertexDoesNotExist`]
+    /// if any of the vertices doesn't exist and [`GraphOperationError::InvalidEdgeLength`]
+    /// if length of a visited edge is negative or NaN
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> =
+    ///     Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let id = |label: &str| graph.get_vertex_id(&label.into());
+    ///
+    /// let (distance, path) = graph
+    ///     .shortest_path(id("Moscow"), id("Vologda"), |&km| km)
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert_eq!(distance, 425);
+    /// assert_eq!(path, [id("Moscow"), id("Yaroslavl"), id("Vologda")]);
+    ///
+    /// // count edges instead of kilometers
+    /// let (hops, _) = graph.shortest_path(id("Moscow"), id("Novgorod"), |_| 1).unwrap().unwrap();
+    /// assert_eq!(hops, 2);
+    ///
+    /// assert_eq!(graph.shortest_path(id("Vologda"), id("Moscow"), |&km| km), Ok(None));
+    /// ```
+    pub fn shortest_path<W, F>(
+        &self,
+        from: VertexId,
+        to: VertexId,
+        weight: F,
+    ) -> Result<Option<(W, Vec<VertexId>)>>
+    where
+        W: Copy + Default + PartialOrd + Add<Output = W>,
+        F: Fn(&E) -> W,
+    {
+        shortest_path(self, from, to, weight)
+    }
+}
@@ -0,0 +1,428 @@
+use std::collections::{HashMap, VecDeque};
+
+use super::{Graph, Label, Neighbors, VertexId, Vertices};
+
+/// Distribution of in- or out-degrees of the vertices
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DegreeDistribution {
+    /// the smallest degree, `0` for empty graph
+    pub min: usize,
+    /// the largest degree, `0` for empty graph
+    pub max: usize,
+    /// average degree, `0.0` for empty graph
+    pub mean: f64,
+    /// `histogram[d]` is count of vertices with degree `d`
+    pub histogram: Vec<usize>,
+}
+
+impl DegreeDistribution {
+    fn new(degrees: &[usize]) -> Self {
+        let Some(&max) = degrees.iter().max() else {
+            return Self::default();
+        };
+        let mut histogram = vec![0; max + 1];
+        for &degree in degrees {
+            histogram[degree] += 1;
+        }
+        Self {
+            min: degrees.iter().min().copied().unwrap_or(0),
+            max,
+            mean: degrees.iter().sum::<usize>() as f64 / degrees.len() as f64,
+            histogram,
+        }
+    }
+}
+
+/// Returns ids of the vertices in insertion order and indices of their successors
+fn successors<G: Vertices + Neighbors>(graph: &G) -> (Vec<G::VertexId>, Vec<Vec<usize>>) {
+    let ids = graph.vertex_ids().collect::<Vec<_>>();
+    let indices = ids
+        .iter()
+        .enumerate()
+        .map(|(index, &vertex_id)| (vertex_id, index))
+        .collect::<HashMap<_, _>>();
+    let successors = ids
+        .iter()
+        .map(|&vertex_id| {
+            let neighbours = graph.neighbors(vertex_id).into_iter().flatten();
+            neighbours
+                .filter_map(|(to, _)| indices.get(&to).copied())
+                .collect()
+        })
+        .collect();
+    (ids, successors)
+}
+
+/// Same as [`Graph::out_degree_distribution`] for any storage which implements
+/// [`Vertices`] and [`Neighbors`]
+pub fn out_degree_distribution<G: Vertices + Neighbors>(graph: &G) -> DegreeDistribution {
+    let (_, successors) = successors(graph);
+    let degrees = successors.iter().map(Vec::len).collect::<Vec<_>>();
+    DegreeDistribution::new(&degrees)
+}
+
+/// Same as [`Graph::in_degree_distribution`] for any storage which implements
+/// [`Vertices`] and [`Neighbors`]
+pub fn in_degree_distribution<G: Vertices + Neighbors>(graph: &G) -> DegreeDistribution {
+    let (_, successors) = successors(graph);
+    let mut degrees = vec![0; successors.len()];
+    for &to in successors.iter().flatten() {
+        degrees[to] += 1;
+    }
+    DegreeDistribution::new(&degrees)
+}
+
+/// Same as [`Graph::density`] for any storage which implements [`Vertices`] and [`Neighbors`]
+pub fn density<G: Vertices + Neighbors>(graph: &G) -> f64 {
+    let (_, successors) = successors(graph);
+    match successors.len() {
+        0 | 1 => 0.0,
+        n => successors.iter().map(Vec::len).sum::<usize>() as f64 / (n * (n - 1)) as f64,
+    }
+}
+
+/// Same as [`Graph::weakly_connected_components`] for any storage which implements
+/// [`Vertices`] and [`Neighbors`]
+///
+/// ```
+/// use simple_graph::{Graph, weakly_connected_components};
+/// use std::str::FromStr;
+///
+/// let mut graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+/// graph.add_vertex("Kazan".into()).unwrap();
+///
+/// let components = weakly_connected_components(&graph.freeze());
+/// assert_eq!(components, [vec![0, 1, 2, 3, 4], vec![5]]);
+/// ```
+pub fn weakly_connected_components<G: Vertices + Neighbors>(graph: &G) -> Vec<Vec<G::VertexId>> {
+    let (ids, successors) = successors(graph);
+
+    // union-find with path halving
+    let mut parents = (0..ids.len()).collect::<Vec<_>>();
+    let find = |parents: &mut Vec<usize>, mut vertex: usize| {
+        while parents[vertex] != vertex {
+            parents[vertex] = parents[parents[vertex]];
+            vertex = parents[vertex];
+        }
+        vertex
+    };
+    for (from, neighbours) in successors.iter().enumerate() {
+        for &to in neighbours {
+            let (a, b) = (find(&mut parents, from), find(&mut parents, to));
+            parents[a.max(b)] = a.min(b);
+        }
+    }
+
+    let mut components = Vec::<Vec<G::VertexId>>::new();
+    let mut positions = HashMap::new();
+    for (vertex, &vertex_id) in ids.iter().enumerate() {
+        let root = find(&mut parents, vertex);
+        let position = *positions.entry(root).or_insert_with(|| {
+            components.push(Vec::new());
+            components.len() - 1
+        });
+        components[position].push(vertex_id);
+    }
+    components
+}
+
+/// Same as [`Graph::strongly_connected_components`] for any storage which implements
+/// [`Vertices`] and [`Neighbors`]
+///
+/// ```
+/// use simple_graph::{Graph, PersistentGraph, strongly_connected_components};
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+/// let moscow = graph.get_vertex_id(&"Moscow".into());
+/// let vladimir = graph.get_vertex_id(&"Vladimir".into());
+///
+/// let mut persistent = PersistentGraph::from(&graph);
+/// persistent.add_edge(vladimir, moscow, 180).unwrap();
+/// let components = strongly_connected_components(&persistent);
+/// assert_eq!(components.len(), 4);
+/// assert_eq!(components[0], [moscow, vladimir]);
+/// ```
+pub fn strongly_connected_components<G: Vertices + Neighbors>(graph: &G) -> Vec<Vec<G::VertexId>> {
+    let (ids, successors) = successors(graph);
+    let n = ids.len();
+
+    // iterative Tarjan's algorithm
+    let mut order = vec![None::<usize>; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut counter = 0;
+    let mut components = Vec::new();
+
+    for root in 0..n {
+        if order[root].is_some() {
+            continue;
+        }
+        let mut calls = vec![(root, 0)];
+        order[root] = Some(counter);
+        low[root] = counter;
+        counter += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some((vertex, next)) = calls.last_mut() {
+            let vertex = *vertex;
+            if let Some(&to) = successors[vertex].get(*next) {
+                *next += 1;
+                match order[to] {
+                    None => {
+                        order[to] = Some(counter);
+                        low[to] = counter;
+                        counter += 1;
+                        stack.push(to);
+                        on_stack[to] = true;
+                        calls.push((to, 0));
+                    }
+                    Some(index) if on_stack[to] => low[vertex] = low[vertex].min(index),
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some(&(parent, _)) = calls.last() {
+                low[parent] = low[parent].min(low[vertex]);
+            }
+            if Some(low[vertex]) == order[vertex] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == vertex {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                components.push(component);
+            }
+        }
+    }
+
+    components.sort_unstable_by_key(|component| component[0]);
+    components
+        .into_iter()
+        .map(|component| component.into_iter().map(|i| ids[i]).collect())
+        .collect()
+}
+
+/// Same as [`Graph::self_loops`] for any storage which implements [`Vertices`] and [`Neighbors`]
+pub fn self_loops<G: Vertices + Neighbors>(graph: &G) -> Vec<G::VertexId> {
+    let (ids, successors) = successors(graph);
+    (0..ids.len())
+        .filter(|&vertex| successors[vertex].contains(&vertex))
+        .map(|vertex| ids[vertex])
+        .collect()
+}
+
+/// Same as [`Graph::isolated_vertices`] for any storage which implements
+/// [`Vertices`] and [`Neighbors`]
+pub fn isolated_vertices<G: Vertices + Neighbors>(graph: &G) -> Vec<G::VertexId> {
+    let (ids, successors) = successors(graph);
+    let mut connected = successors.iter().map(|s| !s.is_empty()).collect::<Vec<_>>();
+    for &to in successors.iter().flatten() {
+        connected[to] = true;
+    }
+    ids.into_iter()
+        .zip(connected)
+        .filter(|(_, connected)| !connected)
+        .map(|(vertex_id, _)| vertex_id)
+        .collect()
+}
+
+/// Same as [`Graph::is_dag`] for any storage which implements [`Vertices`] and [`Neighbors`]
+pub fn is_dag<G: Vertices + Neighbors>(graph: &G) -> bool {
+    let components = strongly_connected_components(graph);
+    self_loops(graph).is_empty() && components.iter().all(|component| component.len() == 1)
+}
+
+/// Same as [`Graph::diameter`] for any storage which implements [`Vertices`] and [`Neighbors`]
+///
+/// ```
+/// use simple_graph::{Graph, diameter};
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+/// assert_eq!(diameter(&graph.freeze()), 2);
+/// ```
+pub fn diameter<G: Vertices + Neighbors>(graph: &G) -> usize {
+    let (ids, successors) = successors(graph);
+    let mut diameter = 0;
+    let mut distances = vec![None; ids.len()];
+    for source in 0..ids.len() {
+        distances.fill(None);
+        distances[source] = Some(0);
+        let mut queue = VecDeque::from([source]);
+        while let Some(vertex) = queue.pop_front() {
+            let distance = distances[vertex].unwrap_or(0);
+            diameter = diameter.max(distance);
+            for &to in &successors[vertex] {
+                if distances[to].is_none() {
+                    distances[to] = Some(distance + 1);
+                    queue.push_back(to);
+                }
+            }
+        }
+    }
+    diameter
+}
+
+impl<V: Label, E: Label> Graph<V, E> {
+    /// Returns distribution of the outgoing edges count
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let out_degrees = graph.out_degree_distribution();
+    ///
+    /// assert_eq!((out_degrees.min, out_degrees.max, out_degrees.mean), (0, 2, 0.8));
+    /// assert_eq!(out_degrees.histogram, [2, 2, 1]);
+    /// ```
+    pub fn out_degree_distribution(&self) -> DegreeDistribution {
+        out_degree_distribution(self)
+    }
+
+    /// Returns distribution of the incoming edges count
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let in_degrees = graph.in_degree_distribution();
+    ///
+    /// assert_eq!((in_degrees.min, in_degrees.max, in_degrees.mean), (0, 1, 0.8));
+    /// assert_eq!(in_degrees.histogram, [1, 4]);
+    /// ```
+    pub fn in_degree_distribution(&self) -> DegreeDistribution {
+        in_degree_distribution(self)
+    }
+
+    /// Returns ratio of the edges count to the maximum possible count of edges
+    /// between different vertices, self-loops and parallel edges can make it greater than `1.0`
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// assert_eq!(graph.density(), 0.2);
+    /// ```
+    pub fn density(&self) -> f64 {
+        density(self)
+    }
+
+    /// Returns groups of vertices which are connected if directions of the edges are ignored,
+    /// vertices and groups keep insertion order
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let mut graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let kazan = graph.add_vertex("Kazan".into()).unwrap();
+    ///
+    /// let components = graph.weakly_connected_components();
+    /// assert_eq!(components.len(), 2);
+    /// assert_eq!(components[0].len(), 5);
+    /// assert_eq!(components[1], [kazan]);
+    /// ```
+    pub fn weakly_connected_components(&self) -> Vec<Vec<VertexId>> {
+        weakly_connected_components(self)
+    }
+
+    /// Returns groups of vertices where every vertex is reachable from every other one,
+    /// vertices and groups keep insertion order
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let mut graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// assert_eq!(graph.strongly_connected_components().len(), 5);
+    ///
+    /// let moscow = graph.get_vertex_id(&"Moscow".into());
+    /// let vladimir = graph.get_vertex_id(&"Vladimir".into());
+    /// let novgorod = graph.get_vertex_id(&"Novgorod".into());
+    /// graph.add_edge(novgorod, moscow, 500).unwrap();
+    ///
+    /// let components = graph.strongly_connected_components();
+    /// assert_eq!(components.len(), 3);
+    /// assert_eq!(components[0], [moscow, vladimir, novgorod]);
+    /// ```
+    pub fn strongly_connected_components(&self) -> Vec<Vec<VertexId>> {
+        strongly_connected_components(self)
+    }
+
+    /// Returns vertices which have an edge to themselves
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let mut graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// assert!(graph.self_loops().is_empty());
+    ///
+    /// let moscow = graph.get_vertex_id(&"Moscow".into());
+    /// graph.add_edge(moscow, moscow, 0).unwrap();
+    /// assert_eq!(graph.self_loops(), [moscow]);
+    /// ```
+    pub fn self_loops(&self) -> Vec<VertexId> {
+        self_loops(self)
+    }
+
+    /// Returns vertices without incoming and outgoing edges
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let mut graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let kazan = graph.add_vertex("Kazan".into()).unwrap();
+    /// assert_eq!(graph.isolated_vertices(), [kazan]);
+    /// ```
+    pub fn isolated_vertices(&self) -> Vec<VertexId> {
+        isolated_vertices(self)
+    }
+
+    /// Returns `true` if the graph has no cycles, self-loops are cycles too
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let mut graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// assert!(graph.is_dag());
+    ///
+    /// let moscow = graph.get_vertex_id(&"Moscow".into());
+    /// let vologda = graph.get_vertex_id(&"Vologda".into());
+    /// graph.add_edge(vologda, moscow, 450).unwrap();
+    /// assert!(!graph.is_dag());
+    /// ```
+    pub fn is_dag(&self) -> bool {
+        is_dag(self)
+    }
+
+    /// Returns the largest count of edges on the shortest path between two vertices,
+    /// only pairs where the second vertex is reachable from the first one are considered
+    ///
+    /// Breadth-first search is started from every vertex, so it takes `O(V * (V + E))` time
+    ///
+    /// ```
+    /// use simple_graph::Graph;
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// assert_eq!(graph.diameter(), 2);
+    /// ```
+    pub fn diameter(&self) -> usize {
+        diameter(self)
+    }
+}
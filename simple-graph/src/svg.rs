@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+
+use super::{Graph, Label, LayoutAlgorithm, Point, escape_xml};
+
+/// Approximate width of one character of the label
+const CHAR_WIDTH: f64 = 7.5;
+/// Horizontal space between label and border of the vertex
+const PADDING: f64 = 10.0;
+/// Height of the vertex
+const NODE_HEIGHT: f64 = 30.0;
+/// Space around the picture
+const MARGIN: f64 = 20.0;
+/// Height of the self-loop above the vertex
+const LOOP_HEIGHT: f64 = 35.0;
+/// Distance between opposite edges which join the same vertices
+const PARALLEL_OFFSET: f64 = 5.0;
+
+/// Formats [`Graph<V, E>`] as SVG image, positions of the vertices are computed by [`Graph::layout`]
+///
+/// Vertices are drawn as rectangles with labels, edges are arrows with labels in the middle
+///
+/// ```
+/// use simple_graph::{Graph, LayoutAlgorithm, Svg};
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, u32> =
+///     Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+///
+/// let svg = Svg::new(&graph).layout(LayoutAlgorithm::ForceDirected).to_string();
+/// assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+/// assert!(svg.contains(">Moscow</text>"));
+/// assert!(svg.contains(">180</text>"));
+/// assert_eq!(svg.matches("<rect ").count(), 5);
+/// assert_eq!(svg.matches("marker-end=\"url(#arrow)\"").count(), 4);
+/// ```
+pub struct Svg<'a, V: Label, E: Label> {
+    graph: &'a Graph<V, E>,
+    algorithm: LayoutAlgorithm,
+}
+
+impl<'a, V: Label, E: Label> Svg<'a, V, E> {
+    /// Creates SVG formatter with [`LayoutAlgorithm::Auto`] layout
+    pub fn new(graph: &'a Graph<V, E>) -> Self {
+        Self {
+            graph,
+            algorithm: LayoutAlgorithm::default(),
+        }
+    }
+
+    /// Sets algorithm which places vertices on the picture
+    pub fn layout(mut self, algorithm: LayoutAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+}
+
+/// Half of width and height of the rectangle with label
+fn half_size(label: &str) -> Point {
+    Point {
+        x: (label.chars().count() as f64 * CHAR_WIDTH + 2.0 * PADDING) / 2.0,
+        y: NODE_HEIGHT / 2.0,
+    }
+}
+
+/// Finds point where the ray from the center of the rectangle towards `target` crosses its border
+fn border(center: Point, half: Point, target: Point) -> Point {
+    let (dx, dy) = (target.x - center.x, target.y - center.y);
+    let scale = (half.x / dx.abs()).min(half.y / dy.abs());
+    match scale.is_finite() {
+        true => Point {
+            x: center.x + dx * scale,
+            y: center.y + dy * scale,
+        },
+        false => center,
+    }
+}
+
+impl<V: Label, E: Label> Display for Svg<'_, V, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let layout = self.graph.layout(self.algorithm);
+        let mut vertices = Vec::with_capacity(self.graph.vertices_count());
+        for vertex_id in self.graph.vertices.keys() {
+            let label = self.graph.get_vertex(*vertex_id).map_err(|_| fmt::Error)?;
+            let label = label.to_string();
+            let position = *layout.positions.get(vertex_id).ok_or(fmt::Error)?;
+            vertices.push((*vertex_id, position, half_size(&label), label));
+        }
+
+        let widest = vertices
+            .iter()
+            .map(|(_, _, half, _)| half.x)
+            .fold(0.0, f64::max);
+        let origin = Point {
+            x: MARGIN + widest,
+            y: MARGIN + LOOP_HEIGHT + NODE_HEIGHT / 2.0,
+        };
+        let width = layout.width + 2.0 * origin.x;
+        let height = layout.height + origin.y + MARGIN + NODE_HEIGHT / 2.0;
+        let places = vertices
+            .iter()
+            .map(|(vertex_id, p, half, _)| {
+                let center = Point {
+                    x: p.x + origin.x,
+                    y: p.y + origin.y,
+                };
+                (*vertex_id, (center, *half))
+            })
+            .collect::<HashMap<_, _>>();
+        let place = |vertex_id| places.get(&vertex_id).copied().ok_or(fmt::Error);
+
+        writeln!(
+            f,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.1}\" height=\"{height:.1}\" \
+             viewBox=\"0 0 {width:.1} {height:.1}\" font-family=\"sans-serif\" font-size=\"13\">"
+        )?;
+        writeln!(f, "  <defs>")?;
+        writeln!(
+            f,
+            "    <marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+             markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\">"
+        )?;
+        writeln!(f, "      <path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"#555\"/>")?;
+        writeln!(f, "    </marker>")?;
+        writeln!(f, "  </defs>")?;
+
+        for neighbours in self.graph.vertices.values() {
+            for ([from, to], edge) in neighbours {
+                let (source, source_half) = place(*from)?;
+                let label = escape_xml(&edge.to_string());
+                let middle = if from == to {
+                    let top = source.y - source_half.y;
+                    writeln!(
+                        f,
+                        "  <path d=\"M {:.1} {top:.1} C {:.1} {:.1}, {:.1} {:.1}, {:.1} {top:.1}\" \
+                         fill=\"none\" stroke=\"#555\" marker-end=\"url(#arrow)\"/>",
+                        source.x - 10.0,
+                        source.x - 25.0,
+                        top - LOOP_HEIGHT,
+                        source.x + 25.0,
+                        top - LOOP_HEIGHT,
+                        source.x + 10.0,
+                    )?;
+                    Point {
+                        x: source.x,
+                        y: top - LOOP_HEIGHT * 0.75,
+                    }
+                } else {
+                    let (target, target_half) = place(*to)?;
+                    let mut start = border(source, source_half, target);
+                    let mut end = border(target, target_half, source);
+                    // opposite edges are shifted to the right side, so they don't overlap
+                    if self.graph.get_edge(*to, *from).is_ok() {
+                        let (dx, dy) = (end.x - start.x, end.y - start.y);
+                        let length = dx.hypot(dy).max(f64::EPSILON);
+                        let (nx, ny) = (-dy / length, dx / length);
+                        for point in [&mut start, &mut end] {
+                            point.x += nx * PARALLEL_OFFSET;
+                            point.y += ny * PARALLEL_OFFSET;
+                        }
+                    }
+                    writeln!(
+                        f,
+                        "  <line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" \
+                         stroke=\"#555\" marker-end=\"url(#arrow)\"/>",
+                        start.x, start.y, end.x, end.y
+                    )?;
+                    Point {
+                        x: (start.x + end.x) / 2.0,
+                        y: (start.y + end.y) / 2.0,
+                    }
+                };
+                if !label.is_empty() {
+                    writeln!(
+                        f,
+                        "  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" fill=\"#333\" \
+                         stroke=\"#fff\" stroke-width=\"3\" paint-order=\"stroke\">{label}</text>",
+                        middle.x, middle.y
+                    )?;
+                }
+            }
+        }
+
+        for (vertex_id, _, _, label) in &vertices {
+            let (center, half) = place(*vertex_id)?;
+            writeln!(
+                f,
+                "  <rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"6\" \
+                 fill=\"#fff\" stroke=\"#333\"/>",
+                center.x - half.x,
+                center.y - half.y,
+                2.0 * half.x,
+                2.0 * half.y
+            )?;
+            writeln!(
+                f,
+                "  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" \
+                 dominant-baseline=\"central\">{}</text>",
+                center.x,
+                center.y,
+                escape_xml(label)
+            )?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
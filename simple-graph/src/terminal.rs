@@ -0,0 +1,427 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{self, Display, Formatter};
+
+use super::{
+    Graph, GraphOperationError, HORIZONTAL_SPACING, Label, LayoutAlgorithm, Result,
+    VERTICAL_SPACING, VertexId,
+};
+
+/// Characters used to draw trees and boxes in the terminal
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum Charset {
+    /// box-drawing characters like `├──` and `┌─┐`
+    #[default]
+    Unicode,
+    /// plain ASCII characters like `|--` and `+-+` for terminals without Unicode support
+    Ascii,
+}
+
+/// Order of visiting vertices, see [`Graph::spanning_tree`]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum Traversal {
+    /// the same order as [`Graph::bfs`]
+    #[default]
+    BreadthFirst,
+    /// the same order as [`Graph::dfs`]
+    DepthFirst,
+}
+
+struct TreeNode<'a, V: Label, E: Label> {
+    vertex: &'a V,
+    edge: Option<&'a E>,
+    children: Vec<usize>,
+}
+
+/// Tree of the vertices which were reached by [`Graph::bfs`] or [`Graph::dfs`],
+/// every vertex is a child of the vertex that discovered it
+///
+/// Implements [`Display`] which draws indented tree, labels of the edges are written in parentheses
+pub struct SpanningTree<'a, V: Label, E: Label> {
+    nodes: Vec<TreeNode<'a, V, E>>,
+    charset: Charset,
+}
+
+impl<V: Label, E: Label> SpanningTree<'_, V, E> {
+    /// Sets characters used to draw the tree
+    pub fn charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// Returns labels of the vertices in order of visiting
+    pub fn vertices(&self) -> impl Iterator<Item = &V> {
+        self.nodes.iter().map(|node| node.vertex)
+    }
+}
+
+impl<V: Label, E: Label> Display for SpanningTree<'_, V, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (branch, last, pipe) = match self.charset {
+            Charset::Unicode => ("├── ", "└── ", "│   "),
+            Charset::Ascii => ("|-- ", "`-- ", "|   "),
+        };
+        let Some(root) = self.nodes.first() else {
+            return Ok(());
+        };
+        writeln!(f, "{}", root.vertex)?;
+
+        // (node, prefix of the line, is the last child)
+        let mut stack = root
+            .children
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, &child)| (child, String::new(), i == 0))
+            .collect::<Vec<_>>();
+        while let Some((index, prefix, is_last)) = stack.pop() {
+            let node = &self.nodes[index];
+            write!(
+                f,
+                "{prefix}{}{}",
+                if is_last { last } else { branch },
+                node.vertex
+            )?;
+            match node.edge.map(ToString::to_string) {
+                Some(edge) if !edge.is_empty() => writeln!(f, " ({edge})")?,
+                _ => writeln!(f)?,
+            }
+
+            let prefix = prefix + if is_last { "    " } else { pipe };
+            for (i, &child) in node.children.iter().rev().enumerate() {
+                stack.push((child, prefix.clone(), i == 0));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<V: Label, E: Label> Graph<V, E> {
+    /// Builds spanning tree of the vertices reachable from `source`,
+    /// returns [`GraphOperationError::VertexDoesNotExist`] if there is no such vertex
+    ///
+    /// ```
+    /// use simple_graph::{Charset, Graph, Traversal};
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> =
+    ///     Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let moscow = graph.get_vertex_id(&"Moscow".into());
+    ///
+    /// let tree = graph.spanning_tree(moscow, Traversal::DepthFirst).unwrap();
+    /// let expected = concat!(
+    ///     "Moscow\n",
+    ///     "├── Yaroslavl (250)\n",
+    ///     "│   └── Vologda (175)\n",
+    ///     "└── Vladimir (180)\n",
+    ///     "    └── Novgorod (225)\n",
+    /// );
+    /// assert_eq!(tree.to_string(), expected);
+    ///
+    /// let tree = graph.spanning_tree(moscow, Traversal::BreadthFirst).unwrap();
+    /// let expected = concat!(
+    ///     "Moscow\n",
+    ///     "|-- Vladimir (180)\n",
+    ///     "|   `-- Novgorod (225)\n",
+    ///     "`-- Yaroslavl (250)\n",
+    ///     "    `-- Vologda (175)\n",
+    /// );
+    /// assert_eq!(tree.charset(Charset::Ascii).to_string(), expected);
+    /// ```
+    pub fn spanning_tree(
+        &self,
+        source: VertexId,
+        traversal: Traversal,
+    ) -> Result<SpanningTree<'_, V, E>> {
+        self.get_vertex(source)?;
+
+        let mut nodes: Vec<TreeNode<'_, V, E>> = Vec::new();
+        let mut parents: HashMap<VertexId, (usize, &E)> = HashMap::new();
+        let mut discovered = HashSet::from([source]);
+        let mut queue = VecDeque::from([source]);
+
+        loop {
+            let vertex_id = match traversal {
+                Traversal::BreadthFirst => queue.pop_front(),
+                Traversal::DepthFirst => queue.pop_back(),
+            };
+            let Some(vertex_id) = vertex_id else {
+                break;
+            };
+
+            let index = nodes.len();
+            let parent = parents.get(&vertex_id).copied();
+            nodes.push(TreeNode {
+                vertex: self.get_vertex(vertex_id)?,
+                edge: parent.map(|(_, edge)| edge),
+                children: Vec::new(),
+            });
+            if let Some((parent, _)) = parent {
+                nodes[parent].children.push(index);
+            }
+
+            let neighbours = self
+                .vertices
+                .get(&vertex_id)
+                .ok_or(GraphOperationError::VertexDoesNotExist)?;
+            for ([_, to], edge) in neighbours {
+                if discovered.insert(*to) {
+                    parents.insert(*to, (index, edge));
+                    queue.push_back(*to);
+                }
+            }
+        }
+
+        Ok(SpanningTree {
+            nodes,
+            charset: Charset::default(),
+        })
+    }
+}
+
+const UP: u8 = 1;
+const DOWN: u8 = 2;
+const LEFT: u8 = 4;
+const RIGHT: u8 = 8;
+
+/// Grid of characters with connections between cells which are turned into lines
+#[derive(Default)]
+struct Canvas {
+    cells: Vec<Vec<char>>,
+    links: Vec<Vec<u8>>,
+}
+
+impl Canvas {
+    fn grow(&mut self, row: usize, column: usize) {
+        if self.cells.len() <= row {
+            self.cells.resize(row + 1, Vec::new());
+            self.links.resize(row + 1, Vec::new());
+        }
+        if self.cells[row].len() <= column {
+            self.cells[row].resize(column + 1, ' ');
+            self.links[row].resize(column + 1, 0);
+        }
+    }
+
+    fn put(&mut self, row: usize, column: usize, c: char) {
+        self.grow(row, column);
+        self.cells[row][column] = c;
+    }
+
+    fn write(&mut self, row: usize, column: usize, s: &str) {
+        for (i, c) in s.chars().enumerate() {
+            self.put(row, column + i, c);
+        }
+    }
+
+    fn link(&mut self, row: usize, column: usize, direction: u8) {
+        self.grow(row, column);
+        self.links[row][column] |= direction;
+    }
+
+    fn is_free(&self, row: usize, columns: impl IntoIterator<Item = usize>) -> bool {
+        columns.into_iter().all(|column| {
+            let cell = self.cells.get(row).and_then(|cells| cells.get(column));
+            let link = self.links.get(row).and_then(|links| links.get(column));
+            cell.is_none_or(|&c| c == ' ') && link.is_none_or(|&l| l == 0)
+        })
+    }
+
+    fn render(&self, f: &mut Formatter<'_>, charset: Charset) -> fmt::Result {
+        for (cells, links) in self.cells.iter().zip(&self.links) {
+            let line = cells
+                .iter()
+                .zip(links)
+                .map(|(&c, &link)| match (c, link) {
+                    (' ', 0) | (_, 0) => c,
+                    (' ', link) => line_char(link, charset),
+                    (c, _) => c,
+                })
+                .collect::<String>();
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+fn line_char(link: u8, charset: Charset) -> char {
+    let vertical = link & (UP | DOWN) != 0;
+    let horizontal = link & (LEFT | RIGHT) != 0;
+    match charset {
+        Charset::Ascii => match (vertical, horizontal) {
+            (true, false) => '|',
+            (false, true) => '-',
+            _ => '+',
+        },
+        Charset::Unicode => match link {
+            l if l == UP | RIGHT => '└',
+            l if l == UP | LEFT => '┘',
+            l if l == DOWN | RIGHT => '┌',
+            l if l == DOWN | LEFT => '┐',
+            l if l == UP | DOWN | RIGHT => '├',
+            l if l == UP | DOWN | LEFT => '┤',
+            l if l == LEFT | RIGHT | DOWN => '┬',
+            l if l == LEFT | RIGHT | UP => '┴',
+            l if l == UP | DOWN | LEFT | RIGHT => '┼',
+            _ if vertical => '│',
+            _ => '─',
+        },
+    }
+}
+
+/// Draws [`Graph<V, E>`] in the terminal as boxes placed in layers by [`LayoutAlgorithm::Layered`],
+/// it's intended for small directed acyclic graphs
+///
+/// Edges between neighbour layers are drawn as lines with arrows and labels,
+/// other edges (self-loops, edges which skip layers or go upwards) are listed below the picture
+///
+/// ```
+/// use simple_graph::{BoxDrawing, Graph};
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, u32> =
+///     Graph::from_str("1 Moscow\n2 Vladimir\n3 Yaroslavl\n4 Vologda\n#\n1 2 180\n1 3 250\n1 4 450\n3 4 175\n").unwrap();
+///
+/// let expected = concat!(
+///     "         ┌────────┐\n",
+///     "         │ Moscow │\n",
+///     "         └────┬───┘\n",
+///     "              │\n",
+///     "       ┌──────┴──────┐\n",
+///     "       ▼ 180         ▼ 250\n",
+///     " ┌──────────┐  ┌───────────┐\n",
+///     " │ Vladimir │  │ Yaroslavl │\n",
+///     " └──────────┘  └─────┬─────┘\n",
+///     "                     │\n",
+///     "              ┌──────┘\n",
+///     "              ▼ 175\n",
+///     "         ┌─────────┐\n",
+///     "         │ Vologda │\n",
+///     "         └─────────┘\n",
+///     "Moscow ──450──▶ Vologda\n",
+/// );
+/// assert_eq!(BoxDrawing::new(&graph).to_string(), expected);
+/// ```
+pub struct BoxDrawing<'a, V: Label, E: Label> {
+    graph: &'a Graph<V, E>,
+    charset: Charset,
+}
+
+impl<'a, V: Label, E: Label> BoxDrawing<'a, V, E> {
+    /// Creates box drawing with [`Charset::Unicode`] characters
+    pub fn new(graph: &'a Graph<V, E>) -> Self {
+        Self {
+            graph,
+            charset: Charset::default(),
+        }
+    }
+
+    /// Sets characters used to draw boxes and lines
+    pub fn charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self
+    }
+}
+
+/// Height of the box with label
+const BOX_HEIGHT: usize = 3;
+/// Height of the space between layers where edges are drawn
+const BAND_HEIGHT: usize = 3;
+
+impl<V: Label, E: Label> Display for BoxDrawing<'_, V, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let layout = self.graph.layout(LayoutAlgorithm::Layered);
+        let mut vertices = HashMap::new();
+        let mut widest = 0;
+        for vertex_id in self.graph.vertices.keys() {
+            let label = self.graph.get_vertex(*vertex_id).map_err(|_| fmt::Error)?;
+            let label = label.to_string();
+            let position = layout.positions.get(vertex_id).ok_or(fmt::Error)?;
+            let layer = (position.y / VERTICAL_SPACING).round() as usize;
+            widest = widest.max(label.chars().count());
+            vertices.insert(*vertex_id, (layer, position.x / HORIZONTAL_SPACING, label));
+        }
+
+        // every vertex gets slot which fits the widest box and small gap
+        let slot = widest + 4 + 1;
+        let center = |x: f64| (x * slot as f64).round() as usize + slot / 2;
+        let top = |layer: usize| layer * (BOX_HEIGHT + BAND_HEIGHT);
+        let (horizontal, vertical, corners, arrow, connector) = match self.charset {
+            Charset::Unicode => ('─', '│', ['┌', '┐', '└', '┘'], '▼', '┬'),
+            Charset::Ascii => ('-', '|', ['+', '+', '+', '+'], 'v', '+'),
+        };
+
+        let mut canvas = Canvas::default();
+        for (layer, x, label) in vertices.values() {
+            let width = label.chars().count() + 4;
+            let (row, left) = (top(*layer), center(*x) - width / 2);
+            let border = horizontal.to_string().repeat(width - 2);
+            canvas.write(row, left, &format!("{}{border}{}", corners[0], corners[1]));
+            canvas.write(row + 1, left, &format!("{vertical} {label} {vertical}"));
+            canvas.write(
+                row + 2,
+                left,
+                &format!("{}{border}{}", corners[2], corners[3]),
+            );
+        }
+
+        let mut labels = Vec::new();
+        let mut others = Vec::new();
+        for neighbours in self.graph.vertices.values() {
+            for ([from, to], edge) in neighbours {
+                let (from_layer, from_x, from_label) = vertices.get(from).ok_or(fmt::Error)?;
+                let (to_layer, to_x, to_label) = vertices.get(to).ok_or(fmt::Error)?;
+                let edge = edge.to_string();
+                if *to_layer != from_layer + 1 {
+                    others.push((from_label, edge, to_label));
+                    continue;
+                }
+
+                let (start, end) = (center(*from_x), center(*to_x));
+                let band = top(*from_layer) + BOX_HEIGHT;
+                canvas.put(band - 1, start, connector);
+                canvas.link(band, start, UP | DOWN);
+                if start == end {
+                    canvas.link(band + 1, start, UP | DOWN);
+                } else {
+                    let (toward, back) = if start < end {
+                        (RIGHT, LEFT)
+                    } else {
+                        (LEFT, RIGHT)
+                    };
+                    canvas.link(band + 1, start, UP | toward);
+                    canvas.link(band + 1, end, DOWN | back);
+                    for column in start.min(end) + 1..start.max(end) {
+                        canvas.link(band + 1, column, LEFT | RIGHT);
+                    }
+                }
+                canvas.put(band + 2, end, arrow);
+                labels.push((band + 2, end, edge, from_label, to_label));
+            }
+        }
+
+        // labels are placed after all arrows, so they never hide any of them
+        for (row, column, edge, from_label, to_label) in labels {
+            let len = edge.chars().count();
+            if len == 0 {
+                continue;
+            }
+            match canvas.is_free(row, column + 1..column + len + 3) {
+                true => canvas.write(row, column + 2, &edge),
+                false => others.push((from_label, edge, to_label)),
+            }
+        }
+
+        canvas.render(f, self.charset)?;
+        let (line, head) = match self.charset {
+            Charset::Unicode => ("──", "──▶"),
+            Charset::Ascii => ("--", "-->"),
+        };
+        for (from, edge, to) in others {
+            match edge.is_empty() {
+                true => writeln!(f, "{from} {head} {to}")?,
+                false => writeln!(f, "{from} {line}{edge}{head} {to}")?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,447 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use super::{
+    Graph, GraphOperationError, Label, ParseGraphError, Result, TgfEvent, TgfGraphBuilder,
+    TgfIndices, TgfOptions, parse_index, parse_label,
+};
+
+/// Meaning of the single line in [`TgfDocument`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TgfItem {
+    /// `1 vertex label`, label is kept as it was written
+    Vertex { index: usize, label: String },
+    /// first `#` line which separates vertex definitions from edge definitions
+    Separator,
+    /// `1 2 edge label`, label is kept as it was written
+    Edge {
+        from: usize,
+        to: usize,
+        label: String,
+    },
+    /// blank line or any `#` line after the separator, it doesn't affect the graph
+    Trivia,
+}
+
+/// Single line of [`TgfDocument`] with its original text
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct TgfLine {
+    item: TgfItem,
+    text: String,
+}
+
+impl TgfLine {
+    fn vertex(index: usize, label: String) -> Self {
+        Self {
+            text: format!("{index} {label}"),
+            item: TgfItem::Vertex { index, label },
+        }
+    }
+
+    fn edge(from: usize, to: usize, label: String) -> Self {
+        Self {
+            text: format!("{from} {to} {label}"),
+            item: TgfItem::Edge { from, to, label },
+        }
+    }
+
+    fn separator() -> Self {
+        Self {
+            item: TgfItem::Separator,
+            text: "#".into(),
+        }
+    }
+}
+
+/// Lossless representation of the Trivial Graph Format file
+///
+/// Unlike [`Graph<V, E>`] it keeps original vertex indices, order of the definitions,
+/// blank lines and comments, so editing tools can change a file and write it back with minimal diffs.
+/// Only edited lines are regenerated, all other lines are written exactly as they were read.
+///
+/// ```
+/// use simple_graph::TgfDocument;
+/// use std::str::FromStr;
+///
+/// let s = concat!(
+///     "10 Moscow\n",
+///     "\n",
+///     "20 Vladimir\n",
+///     "#   edges\n",
+///     "10   20   180\n",
+///     "# end of file\n",
+/// );
+/// let mut document = TgfDocument::from_str(s).unwrap();
+/// assert_eq!(document.to_string(), s);
+///
+/// let yaroslavl = document.add_vertex("Yaroslavl");
+/// document.add_edge(10, yaroslavl, "250").unwrap();
+///
+/// let expected = concat!(
+///     "10 Moscow\n",
+///     "\n",
+///     "20 Vladimir\n",
+///     "21 Yaroslavl\n",
+///     "#   edges\n",
+///     "10   20   180\n",
+///     "10 21 250\n",
+///     "# end of file\n",
+/// );
+/// assert_eq!(document.to_string(), expected);
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct TgfDocument {
+    lines: Vec<TgfLine>,
+    line_ending: LineEnding,
+    trailing_line_ending: bool,
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+enum LineEnding {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+impl TgfDocument {
+    /// Creates new empty document
+    ///
+    /// ```
+    /// use simple_graph::TgfDocument;
+    ///
+    /// let mut document = TgfDocument::new();
+    /// let first = document.add_vertex("First node");
+    /// let second = document.add_vertex("Second node");
+    /// document.add_edge(first, second, "Edge between the two").unwrap();
+    ///
+    /// assert_eq!(document.to_string(), include_str!("../test_input/default.tgf"));
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            trailing_line_ending: true,
+            ..Self::default()
+        }
+    }
+
+    /// Returns items of the document in the order they are written
+    ///
+    /// ```
+    /// use simple_graph::{TgfDocument, TgfItem};
+    /// use std::str::FromStr;
+    ///
+    /// let document = TgfDocument::from_str(include_str!("../test_input/default.tgf")).unwrap();
+    /// let vertices = document
+    ///     .items()
+    ///     .filter(|item| matches!(item, TgfItem::Vertex { .. }))
+    ///     .count();
+    /// assert_eq!(vertices, 2);
+    /// ```
+    pub fn items(&self) -> impl Iterator<Item = &TgfItem> {
+        self.lines.iter().map(|line| &line.item)
+    }
+
+    /// Returns label of the vertex with specified index as it is written in the document
+    ///
+    /// ```
+    /// use simple_graph::TgfDocument;
+    /// use std::str::FromStr;
+    ///
+    /// let document = TgfDocument::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// assert_eq!(document.vertex_label(2), Some("Vladimir"));
+    /// assert_eq!(document.vertex_label(6), None);
+    /// ```
+    pub fn vertex_label(&self, index: usize) -> Option<&str> {
+        self.position_of_vertex(index)
+            .and_then(|position| match &self.lines[position].item {
+                TgfItem::Vertex { label, .. } => Some(label.as_str()),
+                _ => None,
+            })
+    }
+
+    /// Adds vertex after the last vertex definition and returns its index,
+    /// which is greater than any index in the document
+    pub fn add_vertex<L: Display>(&mut self, label: L) -> usize {
+        let index = self
+            .items()
+            .filter_map(|item| match item {
+                TgfItem::Vertex { index, .. } => Some(*index),
+                _ => None,
+            })
+            .max()
+            .map_or(1, |max| max + 1);
+
+        let position = self
+            .lines
+            .iter()
+            .rposition(|line| matches!(line.item, TgfItem::Vertex { .. }))
+            .map(|position| position + 1)
+            .or_else(|| self.position_of_separator())
+            .unwrap_or(self.lines.len());
+
+        self.lines
+            .insert(position, TgfLine::vertex(index, label.to_string()));
+        index
+    }
+
+    /// Changes label of the vertex, returns [`GraphOperationError::VertexDoesNotExist`]
+    /// if there is no vertex with such index
+    ///
+    /// ```
+    /// use simple_graph::TgfDocument;
+    /// use std::str::FromStr;
+    ///
+    /// let mut document = TgfDocument::from_str(include_str!("../test_input/default.tgf")).unwrap();
+    /// document.set_vertex_label(2, "Last node").unwrap();
+    /// assert_eq!(document.vertex_label(2), Some("Last node"));
+    /// ```
+    pub fn set_vertex_label<L: Display>(&mut self, index: usize, label: L) -> Result<()> {
+        let position = self
+            .position_of_vertex(index)
+            .ok_or(GraphOperationError::VertexDoesNotExist)?;
+        self.lines[position] = TgfLine::vertex(index, label.to_string());
+        Ok(())
+    }
+
+    /// Removes vertex and all edges connected to it, returns [`GraphOperationError::VertexDoesNotExist`]
+    /// if there is no vertex with such index
+    ///
+    /// ```
+    /// use simple_graph::TgfDocument;
+    /// use std::str::FromStr;
+    ///
+    /// let mut document = TgfDocument::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// document.remove_vertex(3).unwrap();
+    ///
+    /// let expected = concat!(
+    ///     "1 Moscow\n",
+    ///     "2 Vladimir\n",
+    ///     "4 Novgorod\n",
+    ///     "5 Vologda\n",
+    ///     "#\n",
+    ///     "1 2 180\n",
+    ///     "2 4 225\n",
+    /// );
+    /// assert_eq!(document.to_string(), expected);
+    /// ```
+    pub fn remove_vertex(&mut self, index: usize) -> Result<()> {
+        let position = self
+            .position_of_vertex(index)
+            .ok_or(GraphOperationError::VertexDoesNotExist)?;
+        self.lines.remove(position);
+        self.lines.retain(|line| match line.item {
+            TgfItem::Edge { from, to, .. } => from != index && to != index,
+            _ => true,
+        });
+        Ok(())
+    }
+
+    /// Adds edge after the last edge definition, returns [`GraphOperationError::VertexDoesNotExist`]
+    /// if one of the vertices is not defined
+    pub fn add_edge<L: Display>(&mut self, from: usize, to: usize, label: L) -> Result<()> {
+        if self.position_of_vertex(from).is_none() || self.position_of_vertex(to).is_none() {
+            return Err(GraphOperationError::VertexDoesNotExist);
+        }
+
+        let position = match self
+            .lines
+            .iter()
+            .rposition(|line| matches!(line.item, TgfItem::Edge { .. }))
+        {
+            Some(position) => position + 1,
+            None => match self.position_of_separator() {
+                Some(position) => position + 1,
+                None => {
+                    self.lines.push(TgfLine::separator());
+                    self.lines.len()
+                }
+            },
+        };
+
+        self.lines
+            .insert(position, TgfLine::edge(from, to, label.to_string()));
+        Ok(())
+    }
+
+    /// Changes label of the first edge between two vertices, returns
+    /// [`GraphOperationError::EdgeDoesNotExist`] if there is no such edge
+    ///
+    /// ```
+    /// use simple_graph::TgfDocument;
+    /// use std::str::FromStr;
+    ///
+    /// let mut document = TgfDocument::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// document.set_edge_label(1, 2, 181).unwrap();
+    /// assert!(document.to_string().contains("1 2 181\n"));
+    /// ```
+    pub fn set_edge_label<L: Display>(&mut self, from: usize, to: usize, label: L) -> Result<()> {
+        let position = self
+            .position_of_edge(from, to)
+            .ok_or(GraphOperationError::EdgeDoesNotExist)?;
+        self.lines[position] = TgfLine::edge(from, to, label.to_string());
+        Ok(())
+    }
+
+    /// Removes all edges between two vertices, returns [`GraphOperationError::EdgeDoesNotExist`]
+    /// if there is no such edge
+    ///
+    /// ```
+    /// use simple_graph::{GraphOperationError, TgfDocument};
+    /// use std::str::FromStr;
+    ///
+    /// let mut document = TgfDocument::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// assert!(document.remove_edge(1, 2).is_ok());
+    /// assert_eq!(document.remove_edge(1, 2), Err(GraphOperationError::EdgeDoesNotExist));
+    /// ```
+    pub fn remove_edge(&mut self, from: usize, to: usize) -> Result<()> {
+        self.position_of_edge(from, to)
+            .ok_or(GraphOperationError::EdgeDoesNotExist)?;
+        self.lines.retain(|line| match line.item {
+            TgfItem::Edge {
+                from: edge_from,
+                to: edge_to,
+                ..
+            } => edge_from != from || edge_to != to,
+            _ => true,
+        });
+        Ok(())
+    }
+
+    /// Builds [`Graph<V, E>`] from the document and returns indices of the vertices,
+    /// which can be used with [`crate::TgfWriter::indices`]
+    ///
+    /// ```
+    /// use simple_graph::{Graph, TgfDocument};
+    /// use std::str::FromStr;
+    ///
+    /// let file = include_str!("../test_input/moscow.tgf");
+    /// let document = TgfDocument::from_str(file).unwrap();
+    ///
+    /// let (graph, _indices) = document.to_graph::<String, u32>().unwrap();
+    /// assert_eq!(graph, Graph::from_str(file).unwrap());
+    /// ```
+    pub fn to_graph<V: Label, E: Label>(
+        &self,
+    ) -> Result<(Graph<V, E>, TgfIndices), ParseGraphError> {
+        let mut builder = TgfGraphBuilder::new(&TgfOptions::default());
+        for (n, line) in (1_usize..).zip(self.lines.iter()) {
+            let event = match &line.item {
+                TgfItem::Vertex { index, label } => {
+                    TgfEvent::Vertex(*index, parse_label(label, n)?)
+                }
+                TgfItem::Separator => TgfEvent::Separator,
+                TgfItem::Edge { from, to, label } => {
+                    TgfEvent::Edge(*from, *to, parse_label(label, n)?)
+                }
+                TgfItem::Trivia => continue,
+            };
+            builder.push(event, n)?;
+        }
+        Ok(builder.finish())
+    }
+
+    fn position_of_separator(&self) -> Option<usize> {
+        self.lines
+            .iter()
+            .position(|line| line.item == TgfItem::Separator)
+    }
+
+    fn position_of_vertex(&self, index: usize) -> Option<usize> {
+        self.lines.iter().position(|line| match line.item {
+            TgfItem::Vertex { index: i, .. } => i == index,
+            _ => false,
+        })
+    }
+
+    fn position_of_edge(&self, from: usize, to: usize) -> Option<usize> {
+        self.lines.iter().position(|line| match line.item {
+            TgfItem::Edge {
+                from: edge_from,
+                to: edge_to,
+                ..
+            } => edge_from == from && edge_to == to,
+            _ => false,
+        })
+    }
+}
+
+impl FromStr for TgfDocument {
+    type Err = ParseGraphError;
+
+    /// Parses [`TgfDocument`] from [`&str`] in Trivial Graph Format, labels are not parsed
+    /// until [`TgfDocument::to_graph`] is called
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = Vec::new();
+        let mut separated = false;
+
+        for (n, text) in (1_usize..).zip(s.lines()) {
+            let item = if text.starts_with('#') {
+                if separated {
+                    TgfItem::Trivia
+                } else {
+                    separated = true;
+                    TgfItem::Separator
+                }
+            } else if text.trim().is_empty() {
+                TgfItem::Trivia
+            } else {
+                let mut it = text.split_whitespace();
+                if separated {
+                    let (from, to) = it
+                        .next()
+                        .zip(it.next())
+                        .ok_or(ParseGraphError::EdgeDefinition(n))?;
+                    TgfItem::Edge {
+                        from: parse_index(from, n)?,
+                        to: parse_index(to, n)?,
+                        label: it.remainder().unwrap_or("").into(),
+                    }
+                } else {
+                    let index = it.next().ok_or(ParseGraphError::VertexDefinition(n))?;
+                    TgfItem::Vertex {
+                        index: parse_index(index, n)?,
+                        label: it.remainder().unwrap_or("").into(),
+                    }
+                }
+            };
+            lines.push(TgfLine {
+                item,
+                text: text.into(),
+            });
+        }
+
+        let line_ending = if s.contains("\r\n") {
+            LineEnding::CrLf
+        } else {
+            LineEnding::Lf
+        };
+
+        Ok(Self {
+            lines,
+            line_ending,
+            trailing_line_ending: s.is_empty() || s.ends_with('\n'),
+        })
+    }
+}
+
+impl Display for TgfDocument {
+    /// Formats document as string in Trivial Graph Format, unchanged lines are written as they were read
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let line_ending = self.line_ending.as_str();
+        for (n, line) in self.lines.iter().enumerate() {
+            f.write_str(&line.text)?;
+            if n + 1 < self.lines.len() || self.trailing_line_ending {
+                f.write_str(line_ending)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+use std::borrow::Cow;
+
+/// What to do when the same vertex is defined twice in Trivial Graph Format,
+/// either with the same index or with the same label
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum DuplicateVertexPolicy {
+    /// return [`crate::ParseGraphError::VertexAlreadyDefined`] or [`crate::ParseGraphError::GraphError`]
+    #[default]
+    Error,
+    /// keep the first definition and ignore the following ones
+    KeepFirst,
+    /// replace previous definition with the last one
+    KeepLast,
+}
+
+/// Options of the Trivial Graph Format dialect accepted by [`crate::TgfReader`]
+///
+/// Default options describe the strict format which is also used by
+/// [`impl<V, E> FromStr for Graph<V, E>`](crate::Graph#impl-FromStr-for-Graph<V,+E>)
+///
+/// ```
+/// use simple_graph::{DuplicateVertexPolicy, Graph, TgfOptions, TgfReader};
+///
+/// let s = concat!(
+///     "// cities\n",
+///     "1 Moscow\n",
+///     "\n",
+///     "2 \"  Vladimir\"\n",
+///     "2 Vladimir\n",
+///     "#\n",
+///     "1 2 180\n",
+///     "1 3 250\n",
+/// );
+/// let options = TgfOptions::new()
+///     .ignore_blank_lines(true)
+///     .comment_prefix("//")
+///     .quoted_labels(true)
+///     .create_missing_vertices(true)
+///     .duplicate_vertices(DuplicateVertexPolicy::KeepLast);
+///
+/// let graph: Graph<String, u32> = TgfReader::with_options(s.as_bytes(), options)
+///     .read_graph()
+///     .unwrap();
+///
+/// let moscow = graph.get_vertex_id(&"Moscow".into());
+/// let vladimir = graph.get_vertex_id(&"Vladimir".into());
+/// let auto_created = graph.get_vertex_id(&"3".into());
+///
+/// assert_eq!(graph.vertices_count(), 3);
+/// assert_eq!(graph.get_edge_value(moscow, vladimir), Ok(&180));
+/// assert_eq!(graph.get_edge_value(moscow, auto_created), Ok(&250));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TgfOptions {
+    pub(crate) ignore_blank_lines: bool,
+    pub(crate) comment_prefixes: Vec<String>,
+    pub(crate) quoted_labels: bool,
+    pub(crate) allow_empty_labels: bool,
+    pub(crate) create_missing_vertices: bool,
+    pub(crate) duplicate_vertices: DuplicateVertexPolicy,
+}
+
+impl Default for TgfOptions {
+    fn default() -> Self {
+        Self {
+            ignore_blank_lines: false,
+            comment_prefixes: Vec::new(),
+            quoted_labels: false,
+            allow_empty_labels: true,
+            create_missing_vertices: false,
+            duplicate_vertices: DuplicateVertexPolicy::default(),
+        }
+    }
+}
+
+impl TgfOptions {
+    /// Creates options of the strict Trivial Graph Format
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Skips lines which contain only whitespace instead of returning an error
+    pub fn ignore_blank_lines(mut self, value: bool) -> Self {
+        self.ignore_blank_lines = value;
+        self
+    }
+
+    /// Skips lines which start with `prefix`, for example `//` or `;`.
+    /// Can be called several times to allow different prefixes
+    pub fn comment_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.comment_prefixes.push(prefix.into());
+        self
+    }
+
+    /// Allows labels in double quotes, such as `"  label with spaces "` or `""`.
+    /// Inside quotes `\"`, `\\`, `\n`, `\r` and `\t` escape sequences are supported
+    ///
+    /// ```
+    /// use simple_graph::{TgfEvent, TgfOptions, TgfReader};
+    ///
+    /// let s = "1 \"  quoted \\\"label\\\"\"\n";
+    /// let mut reader = TgfReader::<_, String, String>::with_options(
+    ///     s.as_bytes(),
+    ///     TgfOptions::new().quoted_labels(true),
+    /// );
+    /// assert_eq!(
+    ///     reader.next(),
+    ///     Some(Ok(TgfEvent::Vertex(1, "  quoted \"label\"".into())))
+    /// );
+    /// ```
+    pub fn quoted_labels(mut self, value: bool) -> Self {
+        self.quoted_labels = value;
+        self
+    }
+
+    /// Allows vertices and edges without label, enabled by default.
+    /// If disabled, empty label can still be written explicitly as `""` with [`TgfOptions::quoted_labels`]
+    ///
+    /// ```
+    /// use simple_graph::{ParseGraphError, TgfOptions, TgfReader};
+    ///
+    /// let s = "1\n";
+    /// let mut reader = TgfReader::<_, String, String>::with_options(
+    ///     s.as_bytes(),
+    ///     TgfOptions::new().allow_empty_labels(false),
+    /// );
+    /// assert_eq!(reader.next(), Some(Err(ParseGraphError::ParseLabel(1))));
+    /// ```
+    pub fn allow_empty_labels(mut self, value: bool) -> Self {
+        self.allow_empty_labels = value;
+        self
+    }
+
+    /// Creates vertices which are used in edge definitions but not defined,
+    /// label of such vertex is parsed from its index
+    pub fn create_missing_vertices(mut self, value: bool) -> Self {
+        self.create_missing_vertices = value;
+        self
+    }
+
+    /// Sets what to do with duplicate vertex definitions, see [`DuplicateVertexPolicy`]
+    pub fn duplicate_vertices(mut self, policy: DuplicateVertexPolicy) -> Self {
+        self.duplicate_vertices = policy;
+        self
+    }
+
+    /// Returns `true` if the line doesn't contain any definition and must be skipped
+    pub(crate) fn is_skipped(&self, line: &str) -> bool {
+        (self.ignore_blank_lines && line.trim().is_empty())
+            || self
+                .comment_prefixes
+                .iter()
+                .any(|prefix| line.trim_start().starts_with(prefix.as_str()))
+    }
+
+    /// Returns text of the label taking quotes into account, `None` if label is malformed or
+    /// empty labels are not allowed
+    pub(crate) fn unquote(&self, s: &str) -> Option<String> {
+        if self.quoted_labels && s.starts_with('"') {
+            return unquote(s);
+        }
+        if s.is_empty() && !self.allow_empty_labels {
+            return None;
+        }
+        Some(s.into())
+    }
+}
+
+/// Quotes the label if it can't be read back as is: it contains line breaks, starts with `#` or `"`,
+/// or has leading or trailing whitespace. Result is read by [`TgfOptions::quoted_labels`]
+pub(crate) fn quote(label: &str) -> Cow<'_, str> {
+    let plain =
+        !label.contains(['\n', '\r']) && !label.starts_with(['#', '"']) && label.trim() == label;
+    if plain {
+        return Cow::Borrowed(label);
+    }
+    let mut quoted = String::with_capacity(label.len() + 2);
+    quoted.push('"');
+    for c in label.chars() {
+        match c {
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '"' | '\\' => quoted.extend(['\\', c]),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    Cow::Owned(quoted)
+}
+
+fn unquote(s: &str) -> Option<String> {
+    let mut label = String::new();
+    let mut chars = s.strip_prefix('"')?.chars();
+    loop {
+        match chars.next()? {
+            '"' => return chars.as_str().trim().is_empty().then_some(label),
+            '\\' => label.push(match chars.next()? {
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                c @ ('"' | '\\') => c,
+                _ => return None,
+            }),
+            c => label.push(c),
+        }
+    }
+}
@@ -0,0 +1,413 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use super::{
+    DuplicateVertexPolicy, Graph, Label, ParseGraphError, TgfDiagnostic, TgfOptions, VertexId,
+};
+
+/// Indices of the vertices in the Trivial Graph Format file, see [`TgfReader::read_graph_with_indices`]
+pub type TgfIndices = HashMap<VertexId, usize>;
+
+/// Single definition read from the Trivial Graph Format stream
+#[derive(Debug, Eq, PartialEq)]
+pub enum TgfEvent<V, E> {
+    /// `1 vertex label`, `(index: usize, label: V)`
+    Vertex(usize, V),
+    /// `#` line which separates vertex definitions from edge definitions
+    Separator,
+    /// `1 2 edge label`, `(from: usize, to: usize, label: E)`
+    Edge(usize, usize, E),
+}
+
+/// Trivial Graph Format has two types of definitions which is separated by `#` char
+#[derive(Ord, PartialOrd, Eq, PartialEq)]
+enum ParserMode {
+    /// `1 vertex label`, `<id: usize> <label: &str>`
+    VertexDefinitions,
+    /// `1 2 edge label`, `<from: usize> <to: usize> <label: &str>`
+    EdgeDefinitions,
+}
+
+pub(crate) fn parse_index(s: &str, line: usize) -> Result<usize, ParseGraphError> {
+    s.parse().map_err(|_| ParseGraphError::ParseInt(line))
+}
+
+pub(crate) fn parse_label<T: FromStr>(s: &str, line: usize) -> Result<T, ParseGraphError> {
+    s.parse::<T>()
+        .map_err(|_| ParseGraphError::ParseLabel(line))
+}
+
+/// Builds [`Graph<V, E>`] from the sequence of [`TgfEvent`]s
+pub(crate) struct TgfGraphBuilder<V: Label, E: Label> {
+    graph: Graph<V, E>,
+    vertices: HashMap<usize, VertexId>,
+    indices: TgfIndices,
+    create_missing_vertices: bool,
+    duplicate_vertices: DuplicateVertexPolicy,
+}
+
+impl<V: Label, E: Label> TgfGraphBuilder<V, E> {
+    pub(crate) fn new(options: &TgfOptions) -> Self {
+        Self {
+            graph: Graph::new(),
+            vertices: HashMap::new(),
+            indices: TgfIndices::new(),
+            create_missing_vertices: options.create_missing_vertices,
+            duplicate_vertices: options.duplicate_vertices,
+        }
+    }
+
+    /// Applies single definition which was found at line `n`
+    pub(crate) fn push(&mut self, event: TgfEvent<V, E>, n: usize) -> Result<(), ParseGraphError> {
+        match event {
+            TgfEvent::Vertex(index, label) => self.push_vertex(index, label, n)?,
+            TgfEvent::Separator => {}
+            TgfEvent::Edge(from, to, label) => {
+                if self.create_missing_vertices {
+                    self.create_missing_vertex(from, n)?;
+                    self.create_missing_vertex(to, n)?;
+                }
+
+                let (&from, &to) = self
+                    .vertices
+                    .get(&from)
+                    .zip(self.vertices.get(&to))
+                    .ok_or(ParseGraphError::VerticesNotDefined(from, to, n))?;
+
+                self.graph
+                    .add_edge(from, to, label)
+                    .map_err(|err| ParseGraphError::GraphError(err, n))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn push_vertex(&mut self, index: usize, label: V, n: usize) -> Result<(), ParseGraphError> {
+        if let Some(&previous) = self.vertices.get(&index) {
+            match self.duplicate_vertices {
+                DuplicateVertexPolicy::Error => {}
+                DuplicateVertexPolicy::KeepFirst => return Ok(()),
+                DuplicateVertexPolicy::KeepLast => {
+                    self.vertices.remove(&index);
+                    if !self
+                        .vertices
+                        .values()
+                        .any(|&vertex_id| vertex_id == previous)
+                    {
+                        self.graph
+                            .remove_vertex(previous)
+                            .map_err(|err| ParseGraphError::GraphError(err, n))?;
+                        self.indices.remove(&previous);
+                    }
+                }
+            }
+        }
+
+        // with lenient policies the same label under another index refers to the same vertex
+        let vertex_id = self.graph.get_vertex_id(&label);
+        if self.graph.get_vertex(vertex_id).is_err()
+            || self.duplicate_vertices == DuplicateVertexPolicy::Error
+        {
+            self.graph
+                .add_vertex(label)
+                .map_err(|err| ParseGraphError::GraphError(err, n))?;
+        }
+
+        if self.vertices.insert(index, vertex_id).is_some() {
+            return Err(ParseGraphError::VertexAlreadyDefined(index, n));
+        }
+        self.indices.entry(vertex_id).or_insert(index);
+        Ok(())
+    }
+
+    fn create_missing_vertex(&mut self, index: usize, n: usize) -> Result<(), ParseGraphError> {
+        if !self.vertices.contains_key(&index) {
+            let label = parse_label(&index.to_string(), n)?;
+            self.push_vertex(index, label, n)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn finish(self) -> (Graph<V, E>, TgfIndices) {
+        (self.graph, self.indices)
+    }
+}
+
+/// Streaming parser of the Trivial Graph Format which reads input line by line
+///
+/// Only the current line is kept in memory, so it can be used to process huge files
+/// or to filter definitions without building the whole [`Graph`]
+///
+/// ```
+/// use simple_graph::{TgfEvent, TgfReader};
+///
+/// let s = concat!(
+///     "1 First node\n",
+///     "2 Second node\n",
+///     "#\n",
+///     "1 2 Edge between the two\n",
+/// );
+/// let events = TgfReader::<_, String, String>::new(s.as_bytes())
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(
+///     events,
+///     vec![
+///         TgfEvent::Vertex(1, "First node".into()),
+///         TgfEvent::Vertex(2, "Second node".into()),
+///         TgfEvent::Separator,
+///         TgfEvent::Edge(1, 2, "Edge between the two".into()),
+///     ]
+/// );
+/// ```
+pub struct TgfReader<R, V, E> {
+    reader: R,
+    buffer: String,
+    line: usize,
+    offset: usize,
+    next_offset: usize,
+    mode: ParserMode,
+    options: TgfOptions,
+    /// set after an I/O error, the reader yields nothing after it
+    failed: bool,
+    _marker: PhantomData<fn() -> (V, E)>,
+}
+
+impl<R: BufRead, V: Label, E: Label> TgfReader<R, V, E> {
+    /// Creates new reader over [`BufRead`]
+    ///
+    /// ```
+    /// use simple_graph::TgfReader;
+    /// use std::io::BufReader;
+    ///
+    /// let file = include_bytes!("../test_input/moscow.tgf");
+    /// let _: TgfReader<_, String, u32> = TgfReader::new(BufReader::new(&file[..]));
+    /// ```
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, TgfOptions::default())
+    }
+
+    /// Creates new reader over [`BufRead`] which accepts dialect described by [`TgfOptions`]
+    ///
+    /// ```
+    /// use simple_graph::{TgfOptions, TgfReader};
+    ///
+    /// let s = "; comment\n1 Moscow\n";
+    /// let reader = TgfReader::<_, String, u32>::with_options(
+    ///     s.as_bytes(),
+    ///     TgfOptions::new().comment_prefix(";"),
+    /// );
+    /// assert_eq!(reader.read_graph().unwrap().vertices_count(), 1);
+    /// ```
+    pub fn with_options(reader: R, options: TgfOptions) -> Self {
+        Self {
+            reader,
+            buffer: String::new(),
+            line: 0,
+            offset: 0,
+            next_offset: 0,
+            mode: ParserMode::VertexDefinitions,
+            options,
+            failed: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns number of the last read line, starting from 1
+    ///
+    /// ```
+    /// use simple_graph::TgfReader;
+    ///
+    /// let mut reader = TgfReader::<_, String, u32>::new(&include_bytes!("../test_input/moscow.tgf")[..]);
+    /// assert_eq!(reader.line(), 0);
+    ///
+    /// reader.next();
+    /// assert_eq!(reader.line(), 1);
+    /// ```
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Unwraps this reader, returning the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads all remaining definitions into the [`Graph<V, E>`]
+    ///
+    /// ```
+    /// use simple_graph::{Graph, TgfReader};
+    /// use std::str::FromStr;
+    ///
+    /// let file = include_str!("../test_input/moscow.tgf");
+    ///
+    /// let graph: Graph<String, u32> = TgfReader::new(file.as_bytes()).read_graph().unwrap();
+    /// assert_eq!(graph, Graph::from_str(file).unwrap());
+    /// ```
+    pub fn read_graph(self) -> Result<Graph<V, E>, ParseGraphError> {
+        let (graph, _) = self.read_graph_with_indices()?;
+        Ok(graph)
+    }
+
+    /// Reads all remaining definitions into the [`Graph<V, E>`] and also returns
+    /// indices of the vertices as they were written in the input
+    ///
+    /// ```
+    /// use simple_graph::{Graph, TgfReader};
+    ///
+    /// let s = concat!(
+    ///     "10 First node\n",
+    ///     "20 Second node\n",
+    ///     "#\n",
+    ///     "10 20 Edge between the two\n",
+    /// );
+    /// let (graph, indices) = TgfReader::<_, String, String>::new(s.as_bytes())
+    ///     .read_graph_with_indices()
+    ///     .unwrap();
+    ///
+    /// let second_node_id = graph.get_vertex_id(&"Second node".into());
+    /// assert_eq!(indices.get(&second_node_id), Some(&20));
+    /// ```
+    pub fn read_graph_with_indices(self) -> Result<(Graph<V, E>, TgfIndices), ParseGraphError> {
+        let mut builder = TgfGraphBuilder::new(&self.options);
+
+        let mut reader = self;
+        while let Some(event) = reader.next() {
+            builder.push(event?, reader.line)?;
+        }
+
+        Ok(builder.finish())
+    }
+
+    /// Reads all remaining definitions into the [`Graph<V, E>`], unlike [`TgfReader::read_graph`]
+    /// it doesn't stop at the first error and returns all found problems with their locations
+    ///
+    /// ```
+    /// use simple_graph::{ParseGraphError, TgfReader};
+    /// use std::io::ErrorKind;
+    ///
+    /// let s = concat!(
+    ///     "1 Moscow\n",
+    ///     "x Vladimir\n",
+    ///     "#\n",
+    ///     "1 2 180\n",
+    ///     "1 1 distance\n",
+    /// );
+    /// let diagnostics = TgfReader::<_, String, u32>::new(s.as_bytes())
+    ///     .read_graph_diagnostics()
+    ///     .unwrap_err();
+    ///
+    /// let errors = diagnostics
+    ///     .iter()
+    ///     .map(|diagnostic| (diagnostic.error.clone(), &s[diagnostic.span.clone()]))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     errors,
+    ///     vec![
+    ///         (ParseGraphError::ParseInt(2), "x"),
+    ///         (ParseGraphError::VerticesNotDefined(1, 2, 4), "1 2"),
+    ///         (ParseGraphError::ParseLabel(5), "distance"),
+    ///     ]
+    /// );
+    ///
+    /// // reading stops at the first I/O error
+    /// let invalid_utf8 = TgfReader::<_, String, u32>::new(&b"1 Moscow\n2 \xff\n3 Vladimir\n"[..]);
+    /// let diagnostics = invalid_utf8.read_graph_diagnostics().unwrap_err();
+    /// assert_eq!(diagnostics.len(), 1);
+    /// assert_eq!(diagnostics[0].error, ParseGraphError::Io(ErrorKind::InvalidData, 2));
+    /// ```
+    pub fn read_graph_diagnostics(self) -> Result<Graph<V, E>, Vec<TgfDiagnostic>> {
+        let mut builder = TgfGraphBuilder::new(&self.options);
+        let mut diagnostics = Vec::new();
+
+        let mut reader = self;
+        while let Some(event) = reader.next() {
+            if let Err(err) = event.and_then(|event| builder.push(event, reader.line)) {
+                diagnostics.push(reader.diagnostic(err));
+            }
+        }
+
+        match diagnostics.is_empty() {
+            true => Ok(builder.finish().0),
+            false => Err(diagnostics),
+        }
+    }
+
+    /// Returns location of the error which happened at the last read line
+    pub fn diagnostic(&self, error: ParseGraphError) -> TgfDiagnostic {
+        let is_edge = self.mode == ParserMode::EdgeDefinitions;
+        TgfDiagnostic::new(error, &self.buffer, self.offset, is_edge)
+    }
+
+    fn parse_label<T: FromStr>(&self, s: &str, n: usize) -> Result<T, ParseGraphError> {
+        let label = self
+            .options
+            .unquote(s)
+            .ok_or(ParseGraphError::ParseLabel(n))?;
+        parse_label(&label, n)
+    }
+
+    fn parse_line(&mut self) -> Result<TgfEvent<V, E>, ParseGraphError> {
+        let n = self.line;
+        let line = self.buffer.trim_end_matches(['\n', '\r']);
+
+        if line.starts_with('#') {
+            self.mode = ParserMode::EdgeDefinitions;
+            return Ok(TgfEvent::Separator);
+        }
+
+        let mut it = line.split_whitespace();
+        match self.mode {
+            ParserMode::VertexDefinitions => {
+                let s = it.next().ok_or(ParseGraphError::VertexDefinition(n))?;
+                let index = parse_index(s, n)?;
+                let label = self.parse_label(it.remainder().unwrap_or(""), n)?;
+
+                Ok(TgfEvent::Vertex(index, label))
+            }
+            ParserMode::EdgeDefinitions => {
+                let (from, to) = it
+                    .next()
+                    .zip(it.next())
+                    .ok_or(ParseGraphError::EdgeDefinition(n))?;
+
+                let from = parse_index(from, n)?;
+                let to = parse_index(to, n)?;
+                let label = self.parse_label(it.remainder().unwrap_or(""), n)?;
+
+                Ok(TgfEvent::Edge(from, to, label))
+            }
+        }
+    }
+}
+
+impl<R: BufRead, V: Label, E: Label> Iterator for TgfReader<R, V, E> {
+    type Item = Result<TgfEvent<V, E>, ParseGraphError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        loop {
+            self.buffer.clear();
+            self.offset = self.next_offset;
+            match self.reader.read_line(&mut self.buffer) {
+                Ok(0) => return None,
+                Ok(n) => {
+                    self.line += 1;
+                    self.next_offset += n;
+                    if !self.options.is_skipped(&self.buffer) {
+                        return Some(self.parse_line());
+                    }
+                }
+                Err(err) => {
+                    self.failed = true;
+                    return Some(Err(ParseGraphError::Io(err.kind(), self.line + 1)));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use super::{Graph, GraphOperationError, Label, Result, TgfIndices, VertexId, quote};
+
+/// Order of the definitions produced by [`TgfWriter`]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum TgfOrder {
+    /// vertices and edges are written in the order they were added to the graph
+    #[default]
+    Insertion,
+    /// vertices are sorted by their labels, edges by labels of `(from, to, edge)`
+    Label,
+    /// vertices are sorted by their indices, edges by indices of `(from, to)` and then by label
+    Index,
+}
+
+/// Vertices and edges of the graph with assigned indices, ready to be written in Trivial Graph Format
+pub(crate) struct TgfLayout<'a, V, E> {
+    pub(crate) vertices: Vec<(usize, &'a V)>,
+    pub(crate) edges: Vec<(usize, usize, &'a E)>,
+}
+
+impl<'a, V: Label, E: Label> TgfLayout<'a, V, E> {
+    /// Assigns indices to the vertices and sorts definitions according to `order`
+    ///
+    /// Vertices which are missing in `indices` get new indices after the largest existing one
+    pub(crate) fn new(
+        graph: &'a Graph<V, E>,
+        indices: Option<&TgfIndices>,
+        order: TgfOrder,
+    ) -> Result<Self> {
+        let mut vertices = Vec::with_capacity(graph.vertices_count());
+        for &vertex_id in graph.vertices.keys() {
+            vertices.push((vertex_id, graph.get_vertex(vertex_id)?));
+        }
+
+        if order == TgfOrder::Label {
+            vertices.sort_by_key(|&(_, vertex)| vertex);
+        }
+
+        let mut assigned = HashMap::<VertexId, usize>::with_capacity(vertices.len());
+        match indices {
+            Some(indices) => {
+                let mut next = indices.values().max().map_or(1, |max| max + 1);
+                for &(vertex_id, _) in &vertices {
+                    let index = indices.get(&vertex_id).copied().unwrap_or_else(|| {
+                        next += 1;
+                        next - 1
+                    });
+                    assigned.insert(vertex_id, index);
+                }
+            }
+            None => {
+                for (n, &(vertex_id, _)) in (1_usize..).zip(vertices.iter()) {
+                    assigned.insert(vertex_id, n);
+                }
+            }
+        }
+
+        let mut vertices = vertices
+            .into_iter()
+            .map(|(vertex_id, vertex)| (assigned[&vertex_id], vertex))
+            .collect::<Vec<_>>();
+
+        if order == TgfOrder::Index {
+            vertices.sort_by_key(|&(index, _)| index);
+        }
+
+        let mut edges = Vec::with_capacity(graph.edges_count());
+        for (_, neighbours) in graph.vertices.iter() {
+            for ([from, to], edge) in neighbours {
+                let (&from, &to) = assigned
+                    .get(from)
+                    .zip(assigned.get(to))
+                    .ok_or(GraphOperationError::VertexDoesNotExist)?;
+                edges.push((from, to, edge));
+            }
+        }
+
+        match order {
+            TgfOrder::Insertion => {}
+            TgfOrder::Label => {
+                let position = vertices
+                    .iter()
+                    .enumerate()
+                    .map(|(position, &(index, _))| (index, position))
+                    .collect::<HashMap<_, _>>();
+                edges.sort_by(|&(a_from, a_to, a), &(b_from, b_to, b)| {
+                    (position[&a_from], position[&a_to], a).cmp(&(
+                        position[&b_from],
+                        position[&b_to],
+                        b,
+                    ))
+                });
+            }
+            TgfOrder::Index => edges.sort(),
+        }
+
+        Ok(Self { vertices, edges })
+    }
+}
+
+/// Writes [`Graph<V, E>`] in Trivial Graph Format to the [`Write`]
+///
+/// Unlike [`impl<V, E> Display for Graph<V, E>`](Graph#impl-Display-for-Graph<V,+E>) it allows
+/// to keep the original indices of the vertices and to sort definitions, so the output is stable
+/// and produces small diffs in version control systems
+///
+/// ```
+/// use simple_graph::{Graph, TgfOrder, TgfReader, TgfWriter};
+///
+/// let s = concat!(
+///     "20 Second node\n",
+///     "10 First node\n",
+///     "#\n",
+///     "20 10 Edge between the two\n",
+/// );
+/// let (graph, indices) = TgfReader::<_, String, String>::new(s.as_bytes())
+///     .read_graph_with_indices()
+///     .unwrap();
+///
+/// let mut writer = TgfWriter::new(Vec::new()).indices(&indices);
+/// writer.write_graph(&graph).unwrap();
+/// assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), s);
+///
+/// let mut writer = TgfWriter::new(Vec::new()).order(TgfOrder::Label);
+/// writer.write_graph(&graph).unwrap();
+///
+/// let expected = concat!(
+///     "1 First node\n",
+///     "2 Second node\n",
+///     "#\n",
+///     "2 1 Edge between the two\n",
+/// );
+/// assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), expected);
+/// ```
+pub struct TgfWriter<'a, W> {
+    writer: W,
+    indices: Option<&'a TgfIndices>,
+    order: TgfOrder,
+}
+
+impl<'a, W: Write> TgfWriter<'a, W> {
+    /// Creates new writer which numbers vertices from 1 in insertion order
+    ///
+    /// ```
+    /// use simple_graph::{Graph, TgfWriter};
+    /// use std::str::FromStr;
+    ///
+    /// let file = include_str!("../test_input/moscow.tgf");
+    /// let graph: Graph<String, u32> = Graph::from_str(file).unwrap();
+    ///
+    /// let mut writer = TgfWriter::new(Vec::new());
+    /// writer.write_graph(&graph).unwrap();
+    /// assert_eq!(writer.into_inner(), file.as_bytes());
+    /// ```
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            indices: None,
+            order: TgfOrder::default(),
+        }
+    }
+
+    /// Uses indices returned by [`crate::TgfReader::read_graph_with_indices`] instead of renumbering vertices
+    pub fn indices(mut self, indices: &'a TgfIndices) -> Self {
+        self.indices = Some(indices);
+        self
+    }
+
+    /// Sets order of the vertex and edge definitions, see [`TgfOrder`]
+    pub fn order(mut self, order: TgfOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Writes the whole graph, returns [`io::Error`] if writing failed or graph is inconsistent
+    ///
+    /// Labels with line breaks, leading `#` or `"`, or surrounding whitespace are written in double quotes
+    /// with escape sequences, read them back with [`crate::TgfOptions::quoted_labels`]
+    ///
+    /// ```
+    /// use simple_graph::{Graph, TgfOptions, TgfReader, TgfWriter};
+    ///
+    /// let mut graph: Graph<String, String> = Graph::new();
+    /// let first = graph.add_vertex("two\nlines".into()).unwrap();
+    /// let second = graph.add_vertex("#hashtag".into()).unwrap();
+    /// graph.add_edge(first, second, " \"quoted\" ".into()).unwrap();
+    ///
+    /// let mut writer = TgfWriter::new(Vec::new());
+    /// writer.write_graph(&graph).unwrap();
+    /// let s = writer.into_inner();
+    /// assert_eq!(s, b"1 \"two\\nlines\"\n2 \"#hashtag\"\n#\n1 2 \" \\\"quoted\\\" \"\n");
+    ///
+    /// let options = TgfOptions::new().quoted_labels(true);
+    /// let read: Graph<String, String> = TgfReader::with_options(s.as_slice(), options)
+    ///     .read_graph()
+    ///     .unwrap();
+    /// assert_eq!(read, graph);
+    /// ```
+    pub fn write_graph<V: Label, E: Label>(&mut self, graph: &Graph<V, E>) -> io::Result<()> {
+        let layout = TgfLayout::new(graph, self.indices, self.order).map_err(io::Error::other)?;
+
+        for (index, vertex) in layout.vertices {
+            writeln!(self.writer, "{index} {}", quote(&vertex.to_string()))?;
+        }
+
+        writeln!(self.writer, "#")?;
+
+        for (from, to, edge) in layout.edges {
+            writeln!(self.writer, "{from} {to} {}", quote(&edge.to_string()))?;
+        }
+
+        self.writer.flush()
+    }
+
+    /// Unwraps this writer, returning the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
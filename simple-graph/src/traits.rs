@@ -0,0 +1,203 @@
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+
+use super::Result;
+
+/// Types of the vertices and edges which are shared by other graph traits
+pub trait GraphBase {
+    /// identifies the vertex in the graph, e.g. [`crate::VertexId`] or dense index
+    type VertexId: Copy + Eq + Hash;
+    /// label of the vertex
+    type Vertex;
+    /// label of the edge
+    type Edge;
+
+    /// Returns label of the vertex, returns [`crate::GraphOperationError::VertexDoesNotExist`]
+    /// if it doesn't exist
+    fn vertex(&self, vertex_id: Self::VertexId) -> Result<&Self::Vertex>;
+}
+
+/// Graph which knows count of its vertices
+pub trait VertexCount: GraphBase {
+    /// Returns count of vertices in the graph
+    fn vertices_count(&self) -> usize;
+}
+
+/// Graph which can list all of its vertices
+pub trait Vertices: GraphBase {
+    /// Returns ids of all vertices in the graph in insertion order
+    fn vertex_ids(&self) -> impl Iterator<Item = Self::VertexId>;
+}
+
+/// Graph which can list outgoing edges of the vertex
+pub trait Neighbors: GraphBase {
+    /// Returns adjacent vertices with labels of the edges to them in the order of the edges,
+    /// returns [`crate::GraphOperationError::VertexDoesNotExist`] if the vertex doesn't exist
+    fn neighbors(
+        &self,
+        vertex_id: Self::VertexId,
+    ) -> Result<impl Iterator<Item = (Self::VertexId, &Self::Edge)>>;
+}
+
+/// Graph which can find label of the edge between two vertices
+pub trait EdgeWeights: GraphBase {
+    /// Returns label of the first edge between two vertices,
+    /// returns [`crate::GraphOperationError::EdgeDoesNotExist`] if there is no edge
+    fn edge_weight(&self, from: Self::VertexId, to: Self::VertexId) -> Result<&Self::Edge>;
+}
+
+/// Graph which can be built from scratch
+///
+/// ```
+/// use simple_graph::{Build, EdgeWeights, Graph, PersistentGraph};
+///
+/// fn chain<G: Build<Vertex = usize, Edge = u32>>(length: usize) -> G {
+///     let mut graph = G::default();
+///     let mut previous = graph.add_vertex(0).unwrap();
+///     for i in 1..length {
+///         let vertex = graph.add_vertex(i).unwrap();
+///         graph.add_edge(previous, vertex, i as u32).unwrap();
+///         previous = vertex;
+///     }
+///     graph
+/// }
+///
+/// let graph: Graph<usize, u32> = chain(3);
+/// let persistent: PersistentGraph<usize, u32> = chain(3);
+/// assert_eq!(persistent, PersistentGraph::from(&graph));
+///
+/// let [first, second] = [0, 1].map(|i| graph.get_vertex_id(&i));
+/// assert_eq!(graph.edge_weight(first, second), Ok(&1));
+/// ```
+pub trait Build: GraphBase + Default {
+    /// Adds the vertex, returns [`crate::GraphOperationError::VertexAlreadyExists`]
+    /// if it already exists
+    fn add_vertex(&mut self, vertex: Self::Vertex) -> Result<Self::VertexId>;
+
+    /// Adds the edge, returns [`crate::GraphOperationError::VertexDoesNotExist`]
+    /// if any of the vertices doesn't exist
+    fn add_edge(
+        &mut self,
+        from: Self::VertexId,
+        to: Self::VertexId,
+        edge: Self::Edge,
+    ) -> Result<()>;
+}
+
+/// Generic search algorithm for implementation BFS and DFS.
+/// It uses generic queue type and queue operations to prevent code duplication
+fn generic_search<G, Queue, F>(
+    graph: &G,
+    mut queue: Queue,
+    queue_insert: fn(&mut Queue, G::VertexId),
+    queue_remove: fn(&mut Queue) -> Option<G::VertexId>,
+    source: G::VertexId,
+    mut access: F,
+) -> Result<()>
+where
+    G: Neighbors + VertexCount,
+    F: FnMut(&G::Vertex, Vec<(&G::Vertex, &G::Edge)>),
+{
+    graph.vertex(source)?;
+    let mut visited = HashSet::with_capacity(graph.vertices_count());
+
+    queue_insert(&mut queue, source);
+    visited.insert(source);
+
+    while let Some(vertex) = queue_remove(&mut queue) {
+        let mut adjacent_vertices = Vec::new();
+        for (id, edge) in graph.neighbors(vertex)? {
+            if visited.insert(id) {
+                queue_insert(&mut queue, id);
+            }
+            adjacent_vertices.push((graph.vertex(id)?, edge));
+        }
+        access(graph.vertex(vertex)?, adjacent_vertices);
+    }
+
+    Ok(())
+}
+
+/// Breadth-first search algorithm uses [`VecDeque`] for queue, `access` gets every reachable
+/// vertex with adjacent vertices and labels of the edges to them
+///
+/// Works with any storage which implements [`Neighbors`] and [`VertexCount`]:
+///
+/// ```
+/// use simple_graph::{GraphBase, GraphOperationError, Neighbors, Result, VertexCount, bfs};
+///
+/// /// vertex `i` has edges to vertices `2 * i + 1` and `2 * i + 2`
+/// struct Tree(Vec<&'static str>);
+///
+/// impl GraphBase for Tree {
+///     type VertexId = usize;
+///     type Vertex = &'static str;
+///     type Edge = ();
+///
+///     fn vertex(&self, vertex_id: usize) -> Result<&&'static str> {
+///         self.0.get(vertex_id).ok_or(GraphOperationError::VertexDoesNotExist)
+///     }
+/// }
+///
+/// impl VertexCount for Tree {
+///     fn vertices_count(&self) -> usize {
+///         self.0.len()
+///     }
+/// }
+///
+/// impl Neighbors for Tree {
+///     fn neighbors(&self, vertex_id: usize) -> Result<impl Iterator<Item = (usize, &())>> {
+///         self.vertex(vertex_id)?;
+///         let children = [2 * vertex_id + 1, 2 * vertex_id + 2];
+///         Ok(children.into_iter().filter(|&i| i < self.0.len()).map(|i| (i, &())))
+///     }
+/// }
+///
+/// let mut visited = Vec::new();
+/// let tree = Tree(vec!["a", "b", "c", "d", "e"]);
+/// bfs(&tree, 0, |vertex, children| visited.push((*vertex, children.len()))).unwrap();
+/// assert_eq!(visited, [("a", 2), ("b", 2), ("c", 0), ("d", 0), ("e", 0)]);
+/// ```
+pub fn bfs<G, F>(graph: &G, source: G::VertexId, access: F) -> Result<()>
+where
+    G: Neighbors + VertexCount,
+    F: FnMut(&G::Vertex, Vec<(&G::Vertex, &G::Edge)>),
+{
+    generic_search(
+        graph,
+        VecDeque::new(),
+        |queue, vertex| queue.push_back(vertex),
+        |queue| queue.pop_front(),
+        source,
+        access,
+    )
+}
+
+/// Depth-first search algorithm uses [`Vec`] for stack, `access` gets every reachable
+/// vertex with adjacent vertices and labels of the edges to them
+///
+/// ```
+/// use simple_graph::{Graph, PersistentGraph, dfs};
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+/// let moscow = graph.get_vertex_id(&"Moscow".into());
+///
+/// let mut visited = Vec::new();
+/// dfs(&PersistentGraph::from(&graph), moscow, |vertex, _| visited.push(vertex.clone())).unwrap();
+/// assert_eq!(visited, ["Moscow", "Yaroslavl", "Vologda", "Vladimir", "Novgorod"]);
+/// ```
+pub fn dfs<G, F>(graph: &G, source: G::VertexId, access: F) -> Result<()>
+where
+    G: Neighbors + VertexCount,
+    F: FnMut(&G::Vertex, Vec<(&G::Vertex, &G::Edge)>),
+{
+    generic_search(
+        graph,
+        Vec::new(),
+        |stack, vertex| stack.push(vertex),
+        |stack| stack.pop(),
+        source,
+        access,
+    )
+}
@@ -0,0 +1,469 @@
+use std::hash::Hash;
+
+use linked_hash_map::LinkedHashMap;
+use linked_hash_set::LinkedHashSet;
+
+use super::{Graph, GraphEvent, GraphOperationError, Label, Result, TransactionError, VertexId};
+
+type Edge<E> = ([VertexId; 2], E);
+
+/// Applied change of the graph with everything that's needed to revert it
+#[derive(Debug, Clone)]
+enum Operation<V: Label, E: Label> {
+    AddVertex(VertexId, V),
+    /// `previous` is position of the same edge which was moved to the end
+    AddEdge {
+        edge: Edge<E>,
+        previous: Option<usize>,
+    },
+    /// `incoming` are removed edges of other vertices with their positions
+    RemoveVertex {
+        vertex_id: VertexId,
+        vertex: V,
+        index: usize,
+        edges: LinkedHashSet<Edge<E>>,
+        incoming: Vec<(usize, Edge<E>)>,
+    },
+    RemoveEdge {
+        edge: Edge<E>,
+        position: usize,
+    },
+}
+
+/// Inserts value at the position of the linked set, following values are shifted
+fn insert_at<T: Hash + Eq>(set: &mut LinkedHashSet<T>, position: usize, value: T) {
+    let mut tail = Vec::new();
+    while set.len() > position {
+        tail.extend(set.pop_back());
+    }
+    set.insert(value);
+    set.extend(tail.into_iter().rev());
+}
+
+/// Inserts entry at the position of the linked map, following entries are shifted
+fn insert_entry_at<K: Hash + Eq, T>(map: &mut LinkedHashMap<K, T>, index: usize, key: K, value: T) {
+    let mut tail = Vec::new();
+    while map.len() > index {
+        tail.extend(map.pop_back());
+    }
+    map.insert(key, value);
+    map.extend(tail.into_iter().rev());
+}
+
+/// Wrapper around [`Graph`] which records every change, so a group of changes can be applied
+/// atomically and any change can be undone and redone
+///
+/// Changes made between [`TransactionalGraph::begin`] and [`TransactionalGraph::commit`]
+/// are undone and redone as a whole, other changes are undone one by one. Reverted changes
+/// restore the graph exactly, including order of the vertices and edges
+///
+/// ```
+/// use simple_graph::{Graph, TransactionalGraph};
+/// use std::str::FromStr;
+///
+/// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+/// let mut editor = TransactionalGraph::new(graph.clone());
+///
+/// let moscow = editor.graph().get_vertex_id(&"Moscow".into());
+/// editor.begin().unwrap();
+/// let kazan = editor.add_vertex("Kazan".into()).unwrap();
+/// editor.add_edge(moscow, kazan, 800).unwrap();
+/// editor.remove_vertex(moscow).unwrap();
+/// editor.commit().unwrap();
+/// assert_eq!(editor.graph().vertices_count(), 5);
+///
+/// // the whole transaction is undone, including edges of the removed vertex
+/// editor.undo().unwrap();
+/// assert_eq!(editor.graph(), &graph);
+///
+/// editor.redo().unwrap();
+/// assert!(editor.graph().get_vertex(moscow).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct TransactionalGraph<V: Label, E: Label> {
+    graph: Graph<V, E>,
+    /// changes of the started transaction
+    transaction: Option<Vec<Operation<V, E>>>,
+    undo: Vec<Vec<Operation<V, E>>>,
+    redo: Vec<Vec<Operation<V, E>>>,
+}
+
+impl<V: Label, E: Label> TransactionalGraph<V, E> {
+    /// Wraps the graph, undo and redo history is empty
+    pub fn new(graph: Graph<V, E>) -> Self {
+        Self {
+            graph,
+            transaction: None,
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    /// Returns the current state of the graph
+    pub fn graph(&self) -> &Graph<V, E> {
+        &self.graph
+    }
+
+    /// Returns the current state of the graph, changes of the started transaction are kept
+    pub fn into_graph(self) -> Graph<V, E> {
+        self.graph
+    }
+
+    /// Returns `true` if there are changes which can be undone
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns `true` if there are undone changes which can be redone
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Same as [`Graph::add_vertex`], but the change is recorded
+    pub fn add_vertex(&mut self, vertex: V) -> Result<VertexId> {
+        let vertex_id = self.graph.get_vertex_id(&vertex);
+        self.apply(Operation::AddVertex(vertex_id, vertex))?;
+        Ok(vertex_id)
+    }
+
+    /// Same as [`Graph::add_edge`], but the change is recorded
+    ///
+    /// ```
+    /// use simple_graph::{Graph, TransactionalGraph};
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let mut editor = TransactionalGraph::new(graph.clone());
+    ///
+    /// // adding the existing edge moves it after other edges of the vertex
+    /// let moscow = editor.graph().get_vertex_id(&"Moscow".into());
+    /// let vladimir = editor.graph().get_vertex_id(&"Vladimir".into());
+    /// let edges = |graph: &Graph<String, u32>| -> Vec<u32> {
+    ///     let (_, adjacent_vertices) = graph.get_vertex_info(moscow).unwrap();
+    ///     adjacent_vertices.into_iter().map(|(_, edge)| *edge).collect()
+    /// };
+    /// editor.add_edge(moscow, vladimir, 180).unwrap();
+    /// assert_eq!(edges(editor.graph()), [250, 180]);
+    ///
+    /// editor.undo().unwrap();
+    /// assert_eq!(edges(editor.graph()), [180, 250]);
+    /// ```
+    pub fn add_edge(&mut self, from: VertexId, to: VertexId, edge: E) -> Result<()> {
+        let edge = ([from, to], edge);
+        self.apply(Operation::AddEdge {
+            edge,
+            previous: None,
+        })
+    }
+
+    /// Same as [`Graph::remove_vertex`], but the change is recorded together with removed edges
+    ///
+    /// ```
+    /// use simple_graph::{Graph, TransactionalGraph};
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let mut editor = TransactionalGraph::new(graph.clone());
+    ///
+    /// let vladimir = editor.graph().get_vertex_id(&"Vladimir".into());
+    /// editor.remove_vertex(vladimir).unwrap();
+    /// assert_eq!(editor.graph().edges_count(), 2);
+    ///
+    /// editor.undo().unwrap();
+    /// assert_eq!(editor.graph(), &graph);
+    /// ```
+    pub fn remove_vertex(&mut self, vertex_id: VertexId) -> Result<()> {
+        let vertex = self.graph.get_vertex(vertex_id)?.clone();
+        self.apply(Operation::RemoveVertex {
+            vertex_id,
+            vertex,
+            index: 0,
+            edges: LinkedHashSet::new(),
+            incoming: Vec::new(),
+        })
+    }
+
+    /// Same as [`Graph::remove_edge`], but the change is recorded
+    pub fn remove_edge(&mut self, from: VertexId, to: VertexId) -> Result<()> {
+        let edge = self.graph.get_edge(from, to)?.clone();
+        self.apply(Operation::RemoveEdge { edge, position: 0 })
+    }
+
+    /// Starts transaction, returns [`TransactionError::TransactionAlreadyStarted`]
+    /// if it's already started
+    pub fn begin(&mut self) -> Result<(), TransactionError> {
+        if self.transaction.is_some() {
+            return Err(TransactionError::TransactionAlreadyStarted);
+        }
+        self.transaction = Some(Vec::new());
+        Ok(())
+    }
+
+    /// Finishes transaction, so its changes are undone as a whole,
+    /// returns [`TransactionError::NoTransaction`] if it isn't started
+    pub fn commit(&mut self) -> Result<(), TransactionError> {
+        let operations = self
+            .transaction
+            .take()
+            .ok_or(TransactionError::NoTransaction)?;
+        if !operations.is_empty() {
+            self.undo.push(operations);
+            self.redo.clear();
+        }
+        Ok(())
+    }
+
+    /// Reverts all changes of the transaction,
+    /// returns [`TransactionError::NoTransaction`] if it isn't started
+    ///
+    /// ```
+    /// use simple_graph::{Graph, TransactionalGraph};
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let mut editor = TransactionalGraph::new(graph.clone());
+    ///
+    /// editor.begin().unwrap();
+    /// let kazan = editor.add_vertex("Kazan".into()).unwrap();
+    /// let moscow = editor.graph().get_vertex_id(&"Moscow".into());
+    /// editor.add_edge(moscow, kazan, 800).unwrap();
+    /// editor.rollback().unwrap();
+    ///
+    /// assert_eq!(editor.graph(), &graph);
+    /// assert!(!editor.can_undo());
+    /// ```
+    pub fn rollback(&mut self) -> Result<(), TransactionError> {
+        let operations = self
+            .transaction
+            .take()
+            .ok_or(TransactionError::NoTransaction)?;
+        self.revert(operations);
+        Ok(())
+    }
+
+    /// Runs `changes` in transaction which is committed if they succeed and rolled back otherwise
+    ///
+    /// ```
+    /// use simple_graph::{Graph, GraphOperationError, TransactionError, TransactionalGraph};
+    /// use std::str::FromStr;
+    ///
+    /// let graph: Graph<String, u32> = Graph::from_str(include_str!("../test_input/moscow.tgf")).unwrap();
+    /// let mut editor = TransactionalGraph::new(graph.clone());
+    ///
+    /// let result = editor.transaction(|editor| {
+    ///     let kazan = editor.add_vertex("Kazan".into())?;
+    ///     let new_york = editor.graph().get_vertex_id(&"New York".into());
+    ///     editor.add_edge(kazan, new_york, 9000)
+    /// });
+    ///
+    /// let error = TransactionError::GraphError(GraphOperationError::VertexDoesNotExist);
+    /// assert_eq!(result, Err(error));
+    /// assert_eq!(editor.graph(), &graph);
+    /// ```
+    pub fn transaction<T, F>(&mut self, changes: F) -> Result<T, TransactionError>
+    where
+        F: FnOnce(&mut Self) -> Result<T>,
+    {
+        self.begin()?;
+        match changes(self) {
+            Ok(value) => {
+                self.commit()?;
+                Ok(value)
+            }
+            Err(err) => {
+                self.rollback()?;
+                Err(TransactionError::GraphError(err))
+            }
+        }
+    }
+
+    /// Reverts the last change or transaction, returns [`TransactionError::NothingToUndo`]
+    /// if there are no changes and [`TransactionError::TransactionAlreadyStarted`]
+    /// if transaction isn't finished
+    pub fn undo(&mut self) -> Result<(), TransactionError> {
+        if self.transaction.is_some() {
+            return Err(TransactionError::TransactionAlreadyStarted);
+        }
+        let operations = self.undo.pop().ok_or(TransactionError::NothingToUndo)?;
+        self.redo.push(operations.clone());
+        self.revert(operations);
+        Ok(())
+    }
+
+    /// Applies the last undone change or transaction again, returns
+    /// [`TransactionError::NothingToRedo`] if there are no undone changes
+    /// and [`TransactionError::TransactionAlreadyStarted`] if transaction isn't finished
+    ///
+    /// ```
+    /// use simple_graph::{Graph, TransactionError, TransactionalGraph};
+    ///
+    /// let mut editor = TransactionalGraph::new(Graph::<String, u32>::new());
+    /// editor.add_vertex("Moscow".into()).unwrap();
+    ///
+    /// editor.undo().unwrap();
+    /// assert_eq!(editor.graph().vertices_count(), 0);
+    /// editor.redo().unwrap();
+    /// assert_eq!(editor.graph().vertices_count(), 1);
+    /// assert_eq!(editor.redo(), Err(TransactionError::NothingToRedo));
+    /// ```
+    pub fn redo(&mut self) -> Result<(), TransactionError> {
+        if self.transaction.is_some() {
+            return Err(TransactionError::TransactionAlreadyStarted);
+        }
+        let operations = self.redo.pop().ok_or(TransactionError::NothingToRedo)?;
+        for operation in &operations {
+            self.execute(operation.clone())
+                .map_err(TransactionError::GraphError)?;
+        }
+        self.undo.push(operations);
+        Ok(())
+    }
+
+    /// Executes the operation and records it into the transaction or undo history
+    fn apply(&mut self, operation: Operation<V, E>) -> Result<()> {
+        let operation = self.execute(operation)?;
+        match &mut self.transaction {
+            Some(operations) => operations.push(operation),
+            None => {
+                self.undo.push(vec![operation]);
+                self.redo.clear();
+            }
+        }
+        Ok(())
+    }
+
+    /// Executes the operation and fills in the state which is needed to revert it,
+    /// the graph stays unchanged on error
+    fn execute(&mut self, operation: Operation<V, E>) -> Result<Operation<V, E>> {
+        let graph = &mut self.graph;
+        let operation = match operation {
+            Operation::AddVertex(vertex_id, vertex) => {
+                graph.add_vertex(vertex.clone())?;
+                Operation::AddVertex(vertex_id, vertex)
+            }
+            Operation::AddEdge { edge, .. } => {
+                let [from, to] = edge.0;
+                let previous = graph
+                    .vertices
+                    .get(&from)
+                    .and_then(|edges| edges.iter().position(|e| e == &edge));
+                graph.add_edge(from, to, edge.1.clone())?;
+                Operation::AddEdge { edge, previous }
+            }
+            Operation::RemoveVertex {
+                vertex_id, vertex, ..
+            } => {
+                let index = graph
+                    .vertices
+                    .keys()
+                    .position(|id| *id == vertex_id)
+                    .ok_or(GraphOperationError::VertexDoesNotExist)?;
+                let edges = graph.vertices[&vertex_id].clone();
+                let mut candidates = Vec::new();
+                for (from, neighbours) in graph.vertices.iter() {
+                    for (position, edge) in neighbours.iter().enumerate() {
+                        if *from != vertex_id && edge.0[1] == vertex_id {
+                            candidates.push((position, edge.clone()));
+                        }
+                    }
+                }
+
+                graph.remove_vertex(vertex_id)?;
+                let incoming = candidates
+                    .into_iter()
+                    .filter(|(_, edge)| !graph.vertices[&edge.0[0]].contains(edge))
+                    .collect();
+                Operation::RemoveVertex {
+                    vertex_id,
+                    vertex,
+                    index,
+                    edges,
+                    incoming,
+                }
+            }
+            Operation::RemoveEdge { edge, .. } => {
+                let [from, to] = edge.0;
+                let position = graph
+                    .vertices
+                    .get(&from)
+                    .and_then(|edges| edges.iter().position(|e| e == &edge))
+                    .ok_or(GraphOperationError::EdgeDoesNotExist)?;
+                graph.remove_edge(from, to)?;
+                Operation::RemoveEdge { edge, position }
+            }
+        };
+        Ok(operation)
+    }
+
+    /// Reverts executed operations in reverse order, observers of the graph are notified
+    /// about reverted changes
+    fn revert(&mut self, operations: Vec<Operation<V, E>>) {
+        let graph = &mut self.graph;
+        for operation in operations.into_iter().rev() {
+            match operation {
+                Operation::AddVertex(vertex_id, _) => {
+                    graph.vertices.remove(&vertex_id);
+                    if let Some(vertex) = graph.vertices_data.remove(&vertex_id) {
+                        graph.observers.notify(|| GraphEvent::VertexRemoved {
+                            vertex_id,
+                            vertex,
+                            edges: Vec::new(),
+                        });
+                    }
+                }
+                Operation::AddEdge { edge, previous } => {
+                    if let Some(edges) = graph.vertices.get_mut(&edge.0[0]) {
+                        edges.remove(&edge);
+                        match previous {
+                            Some(position) => insert_at(edges, position, edge),
+                            None => {
+                                let ([from, to], edge) = edge;
+                                graph.observers.notify(|| GraphEvent::EdgeRemoved {
+                                    from,
+                                    to,
+                                    edge,
+                                });
+                            }
+                        }
+                    }
+                }
+                Operation::RemoveVertex {
+                    vertex_id,
+                    vertex,
+                    index,
+                    edges,
+                    incoming,
+                } => {
+                    graph.observers.notify(|| GraphEvent::VertexAdded {
+                        vertex_id,
+                        vertex: vertex.clone(),
+                    });
+                    for ([from, to], edge) in edges.iter().chain(incoming.iter().map(|(_, e)| e)) {
+                        let (from, to) = (*from, *to);
+                        graph.observers.notify(|| GraphEvent::EdgeAdded {
+                            from,
+                            to,
+                            edge: edge.clone(),
+                        });
+                    }
+                    insert_entry_at(&mut graph.vertices, index, vertex_id, edges);
+                    graph.vertices_data.insert(vertex_id, vertex);
+                    for (position, edge) in incoming {
+                        if let Some(edges) = graph.vertices.get_mut(&edge.0[0]) {
+                            insert_at(edges, position, edge);
+                        }
+                    }
+                }
+                Operation::RemoveEdge { edge, position } => {
+                    if let Some(edges) = graph.vertices.get_mut(&edge.0[0]) {
+                        insert_at(edges, position, edge.clone());
+                        let ([from, to], edge) = edge;
+                        graph
+                            .observers
+                            .notify(|| GraphEvent::EdgeAdded { from, to, edge });
+                    }
+                }
+            }
+        }
+    }
+}